@@ -0,0 +1,9 @@
+//! Builds `library/index.json` from `library/catalog.json` with the app's scanning code.
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = skill_studio_lib::catalog_build::run(&args) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
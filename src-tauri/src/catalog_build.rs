@@ -0,0 +1,237 @@
+//! Catalog maintenance, run through the `build-catalog` binary: fetch every catalog repo,
+//! scan it with the app's own scanner, validate what it finds and write `index.json`.
+
+use crate::commands::catalog::parse_catalog;
+use crate::commands::git::{github_url, shallow_clone};
+use crate::commands::skills::{scan_repo_dir, validate_skill_dir};
+use crate::models::{SkillIndex, SkillIndexEntry, SkillRepository};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const INDEX_VERSION: &str = "1.0.0";
+
+const USAGE: &str = "Usage: build-catalog [options]
+
+Fetch every repo in catalog.json, scan it for skills and write index.json.
+
+Options:
+  --library <dir>     Folder with catalog.json, where index.json is written (default: library)
+  --repos <dir>       Where catalog repos are checked out (default: .catalog-cache)
+  --offline           Scan existing checkouts without fetching
+  --check             Validate only, write nothing
+  -h, --help          Show this help";
+
+struct Options {
+    library: PathBuf,
+    repos: PathBuf,
+    offline: bool,
+    check: bool,
+}
+
+// None when help was requested
+fn parse_args(args: &[String]) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        library: PathBuf::from("library"),
+        repos: PathBuf::from(".catalog-cache"),
+        offline: false,
+        check: false,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().map(PathBuf::from).ok_or_else(|| format!("{} needs a value", arg));
+        match arg.as_str() {
+            "--library" => options.library = value()?,
+            "--repos" => options.repos = value()?,
+            "--offline" => options.offline = true,
+            "--check" => options.check = true,
+            "-h" | "--help" => return Ok(None),
+            other => return Err(format!("Unknown option '{}'\n\n{}", other, USAGE)),
+        }
+    }
+    Ok(Some(options))
+}
+
+/// Problems found while building. Errors fail the build, warnings are reported only.
+#[derive(Default)]
+struct Report {
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+// Re-clone next to the old checkout so a failed fetch keeps the previous one
+fn fetch(owner: &str, repo: &str, checkout: &Path) -> Result<(), String> {
+    let staging = checkout.with_file_name(format!("{}.fetching", repo));
+    fs::remove_dir_all(&staging).ok();
+    if let Err(e) = shallow_clone(&github_url(owner, repo), &staging, None) {
+        fs::remove_dir_all(&staging).ok();
+        return Err(e);
+    }
+
+    fs::remove_dir_all(checkout).ok();
+    fs::rename(&staging, checkout).map_err(|e| format!("Failed to replace checkout: {}", e))
+}
+
+fn load_previous_index(path: &Path) -> Option<SkillIndex> {
+    fs::read_to_string(path).ok().and_then(|c| serde_json::from_str(&c).ok())
+}
+
+// YYYY-MM-DD in UTC, the format catalog.json uses for lastUpdated
+fn utc_date() -> String {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = (secs / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn write_json<T: serde::Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    fs::write(path, content + "\n").map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let Some(options) = parse_args(args)? else {
+        println!("{}", USAGE);
+        return Ok(());
+    };
+
+    let catalog_path = options.library.join("catalog.json");
+    let index_path = options.library.join("index.json");
+    let content = fs::read_to_string(&catalog_path)
+        .map_err(|e| format!("Failed to read {}: {}", catalog_path.display(), e))?;
+    let catalog = parse_catalog(&content)?;
+    let previous = load_previous_index(&index_path);
+
+    let mut report = Report::default();
+    let mut repositories: Vec<SkillRepository> = Vec::new();
+    let mut skills: Vec<SkillIndexEntry> = Vec::new();
+    let mut seen_repos: Vec<String> = Vec::new();
+
+    for catalog_repo in &catalog.repos {
+        let url = catalog_repo.url.clone();
+        // parse_catalog already rejected anything that is not owner/repo
+        let Some((owner, repo)) = url.split_once('/') else {
+            continue;
+        };
+        if seen_repos.iter().any(|r| r.eq_ignore_ascii_case(&url)) {
+            report.errors.push(format!("{}: listed more than once", url));
+            continue;
+        }
+        seen_repos.push(url.clone());
+
+        let checkout = options.repos.join(owner).join(repo);
+        if !options.offline {
+            eprintln!("Fetching {}...", url);
+            if let Err(e) = fetch(owner, repo, &checkout) {
+                report.warnings.push(format!("{}: {}", url, e));
+            }
+        }
+
+        if !checkout.exists() {
+            // Keep what the last index knew rather than dropping the repo
+            let same_repo = |o: &str, r: &str| o == owner && r == repo;
+            let kept: Vec<SkillIndexEntry> = previous
+                .iter()
+                .flat_map(|index| index.skills.iter())
+                .filter(|s| same_repo(&s.owner, &s.repo))
+                .cloned()
+                .collect();
+            report.warnings.push(format!(
+                "{}: not checked out, keeping {} skills from the previous index",
+                url,
+                kept.len()
+            ));
+            repositories.extend(
+                previous
+                    .iter()
+                    .flat_map(|index| index.repositories.iter())
+                    .filter(|r| same_repo(&r.owner, &r.repo))
+                    .cloned(),
+            );
+            skills.extend(kept);
+            continue;
+        }
+
+        let mut found = scan_repo_dir(&checkout, owner, repo, catalog_repo.skills_path.as_deref(), &[]);
+        found.sort_by(|a, b| a.path.cmp(&b.path));
+        let skills_path = found.first().map(|s| s.skills_path.clone());
+
+        if found.is_empty() {
+            report.warnings.push(format!("{}: no skills found", url));
+        }
+        if let (Some(declared), Some(detected)) = (&catalog_repo.skills_path, &skills_path) {
+            if declared.trim_end_matches('/') != detected {
+                report.warnings.push(format!(
+                    "{}: skillsPath '{}' does not exist, detected '{}'",
+                    url, declared, detected
+                ));
+            }
+        }
+
+        for skill in found {
+            let mut relative = Path::new(owner).join(repo);
+            for part in [&skill.skills_path, &skill.path].into_iter().filter(|p| p.as_str() != ".") {
+                relative.push(part);
+            }
+            // Listed either way, as the app shows them once fetched
+            if let Err(e) = validate_skill_dir(&options.repos.join(&relative)) {
+                report.warnings.push(format!("{}: {}", skill.id, e));
+            }
+            if skills.iter().any(|s| s.id == skill.id) {
+                report.errors.push(format!("{}: duplicate skill id", skill.id));
+                continue;
+            }
+
+            skills.push(SkillIndexEntry {
+                id: skill.id,
+                name: skill.name,
+                description: skill.description,
+                owner: skill.owner,
+                repo: skill.repo,
+                path: skill.path,
+                local_path: relative.to_string_lossy().to_string(),
+            });
+        }
+
+        repositories.push(SkillRepository {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            branch: None,
+            skills_path,
+        });
+    }
+
+    for warning in &report.warnings {
+        eprintln!("warning: {}", warning);
+    }
+    for error in &report.errors {
+        eprintln!("error: {}", error);
+    }
+    eprintln!("Indexed {} skills from {} repos", skills.len(), repositories.len());
+
+    if !report.errors.is_empty() {
+        return Err(format!("{} errors in {}", report.errors.len(), catalog_path.display()));
+    }
+    if options.check {
+        return Ok(());
+    }
+
+    let index = SkillIndex {
+        version: INDEX_VERSION.to_string(),
+        last_updated: utc_date(),
+        repositories,
+        skills,
+    };
+    write_json(&index_path, &index)?;
+    eprintln!("Index saved to {}", index_path.display());
+    Ok(())
+}
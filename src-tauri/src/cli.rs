@@ -0,0 +1,425 @@
+//! Headless mode: `skill-studio <command>` runs the app's commands without a window and
+//! prints their results as JSON. Config and data are shared with the desktop app.
+
+use crate::commands::health::{catalog_repos, check_catalog, DEFAULT_STALE_DAYS};
+use crate::commands::metadata::repo_metadata;
+use crate::commands::popularity::{ingest_popularity, rank_skills};
+use crate::commands::publish::publish;
+use crate::commands::settings::load_settings;
+use crate::commands::skills::{collect_all_skills, find_resource_dir, set_resource_dir};
+use crate::commands::updates::{apply_updates, run_check, scope_targets};
+use crate::commands::{
+    add_custom_repo, fetch_repo, get_all_repos, get_custom_repos, get_favorites, get_installed_skills,
+    install_skill, open_skill, remove_custom_repo, toggle_favorite_repo, toggle_favorite_skill,
+    uninstall_skill,
+};
+use crate::models::{PublishSkillRequest, Skill};
+use serde::Serialize;
+use std::io::Write;
+use tauri::async_runtime::block_on;
+
+const USAGE: &str = "Usage: skill-studio <command> [options]
+
+Commands:
+  list [--installed] [--fetched]      List skills from the catalog and custom repos
+  show <skill-id>                     Show a skill and its SKILL.md, fetching its repo if needed
+  repos                               List catalog and custom repos
+  popular [--trending]                List skills by install count, or by recent installs
+  refresh-popularity                  Ingest the popularity feed configured in settings
+  fetch <owner/repo>                  Fetch or refresh a repo
+  metadata <owner/repo> [--github]    Show repo details, with stars and topics from GitHub
+  installed [--scope <scope>]         List installed skills per agent
+  install <skill-id>... [--method <copy|native>] [--scope <scope>]
+  uninstall <name>... [--force] [--scope <scope>]
+  check-updates [--no-refresh] [--scope <scope>]
+  update [--scope <scope>]            Update installed skills without local changes
+  favorites                           List favorite skills and repos
+  favorite <skill-id>                 Toggle a favorite skill
+  favorite-repo <owner/repo>          Toggle a favorite repo
+  custom-repos                        List custom repos
+  add-repo <owner/repo>               Add and fetch a custom repo
+  remove-repo <owner/repo>            Remove a custom repo
+  publish <folder|local-skill> <owner/repo|git-url> [--skills-path <path>] [--branch <name>]
+          [--base <branch>] [--message <text>] [--pr]
+                                      Commit a skill to a new branch of a repo and push it
+  check-catalog [--catalog <file>] [--stale-days <days>]
+                                      Report dead, moved, empty and stale catalog repos
+
+<scope> is \"user\" (default) or \"project\". Results are printed as JSON; the exit
+code is 1 if the command, or any item of install/uninstall, failed, and for
+check-catalog if any repo needs attention.";
+
+// Options followed by a value; any other `--option` is a flag
+const VALUE_OPTIONS: [&str; 8] = [
+    "--scope", "--method", "--catalog", "--stale-days", "--skills-path", "--branch", "--base", "--message",
+];
+
+/// Positional arguments, flags and option values of one command.
+struct Args {
+    positional: Vec<String>,
+    flags: Vec<String>,
+    values: Vec<(String, String)>,
+}
+
+impl Args {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut parsed = Args {
+            positional: Vec::new(),
+            flags: Vec::new(),
+            values: Vec::new(),
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if VALUE_OPTIONS.contains(&arg.as_str()) {
+                let value = args.next().cloned().ok_or_else(|| format!("{} needs a value", arg))?;
+                parsed.values.push((arg.clone(), value));
+            } else if arg.starts_with("--") {
+                parsed.flags.push(arg.clone());
+            } else {
+                parsed.positional.push(arg.clone());
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn value(&self, option: &str) -> Option<String> {
+        self.values.iter().rev().find(|(o, _)| o == option).map(|(_, v)| v.clone())
+    }
+
+    fn has(&self, flag: &str) -> bool {
+        self.flags.iter().any(|f| f == flag)
+    }
+
+    fn one(&self, what: &str) -> Result<&str, String> {
+        match self.positional.as_slice() {
+            [value] => Ok(value),
+            _ => Err(format!("Expected one {}", what)),
+        }
+    }
+
+    fn some(&self, what: &str) -> Result<&[String], String> {
+        if self.positional.is_empty() {
+            return Err(format!("Expected at least one {}", what));
+        }
+        Ok(&self.positional)
+    }
+
+    // Every command lists the flags and value options it reads, so a misplaced one is an error
+    fn reject_unknown(&self, known: &[&str]) -> Result<(), String> {
+        let options = self.flags.iter().chain(self.values.iter().map(|(option, _)| option));
+        match options.into_iter().find(|o| !known.contains(&o.as_str())) {
+            Some(option) => Err(format!("Unknown option '{}'", option)),
+            None => Ok(()),
+        }
+    }
+}
+
+fn split_repo(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('/')
+        .filter(|(owner, repo)| !owner.is_empty() && !repo.is_empty())
+        .map(|(owner, repo)| (owner.to_string(), repo.to_string()))
+        .ok_or_else(|| format!("Expected owner/repo, got '{}'", value))
+}
+
+fn install_method(value: &str) -> Result<String, String> {
+    match value {
+        "copy" | "native" => Ok(value.to_string()),
+        _ => Err(format!("Invalid --method '{}', expected copy or native", value)),
+    }
+}
+
+fn find_skill(id: &str) -> Result<Skill, String> {
+    collect_all_skills()
+        .into_iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| format!("Skill {} not found", id))
+}
+
+/// Outcome for one of several items given to a command.
+#[derive(Serialize)]
+struct ItemResult<T> {
+    item: String,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// JSON printed on stdout; `failed` is set when some items of the command failed.
+struct Output {
+    json: String,
+    failed: bool,
+}
+
+fn to_json<T: Serialize>(value: T) -> Result<Output, String> {
+    let json = serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?;
+    Ok(Output { json, failed: false })
+}
+
+fn message(text: String) -> Result<Output, String> {
+    to_json(serde_json::json!({ "message": text }))
+}
+
+// Install or uninstall each item, reporting every outcome instead of stopping at the first error
+fn each<T: Serialize>(items: &[String], job: impl Fn(&str) -> Result<T, String>) -> Result<Output, String> {
+    let results: Vec<ItemResult<T>> = items
+        .iter()
+        .map(|item| {
+            let result = job(item);
+            ItemResult {
+                item: item.clone(),
+                success: result.is_ok(),
+                error: result.as_ref().err().cloned(),
+                result: result.ok(),
+            }
+        })
+        .collect();
+    let failed = results.iter().any(|r| !r.success);
+    to_json(results).map(|output| Output { failed, ..output })
+}
+
+fn execute(command: &str, args: &Args) -> Result<Output, String> {
+    let scope = args.value("--scope");
+
+    match command {
+        "list" => {
+            args.reject_unknown(&["--installed", "--fetched"])?;
+            let skills: Vec<Skill> = collect_all_skills()
+                .into_iter()
+                .filter(|s| !args.has("--installed") || s.is_installed)
+                .filter(|s| !args.has("--fetched") || s.is_fetched)
+                // SKILL.md bodies are left to `show`
+                .map(|s| Skill { content: None, ..s })
+                .collect();
+            to_json(skills)
+        }
+        "show" => {
+            args.reject_unknown(&[])?;
+            to_json(block_on(open_skill(args.one("skill id")?.to_string()))?)
+        }
+        "popular" => {
+            args.reject_unknown(&["--trending"])?;
+            let sort = if args.has("--trending") { "trending" } else { "popular" };
+            let skills = rank_skills(collect_all_skills(), sort, None)?;
+            to_json(skills.into_iter().map(|s| Skill { content: None, ..s }).collect::<Vec<_>>())
+        }
+        "refresh-popularity" => {
+            args.reject_unknown(&[])?;
+            match ingest_popularity()? {
+                Some(info) => to_json(info),
+                None => Err("No popularity source is configured".to_string()),
+            }
+        }
+        "repos" => {
+            args.reject_unknown(&[])?;
+            to_json(block_on(get_all_repos(None, None))?)
+        }
+        "fetch" => {
+            args.reject_unknown(&[])?;
+            let (owner, repo) = split_repo(args.one("repo")?)?;
+            message(block_on(fetch_repo(owner, repo))?)
+        }
+        "metadata" => {
+            args.reject_unknown(&["--github"])?;
+            let (owner, repo) = split_repo(args.one("repo")?)?;
+            to_json(repo_metadata(&owner, &repo, args.has("--github"))?)
+        }
+        "installed" => {
+            args.reject_unknown(&["--scope"])?;
+            to_json(block_on(get_installed_skills(scope))?)
+        }
+        "install" => {
+            args.reject_unknown(&["--method", "--scope"])?;
+            let method = match args.value("--method") {
+                Some(method) => install_method(&method)?,
+                None => load_settings().install_method,
+            };
+            each(args.some("skill id")?, |id| {
+                let skill = find_skill(id)?;
+                block_on(install_skill(
+                    skill.owner,
+                    skill.repo,
+                    skill.name,
+                    skill.path,
+                    skill.skills_path,
+                    method.clone(),
+                    scope.clone(),
+                ))
+            })
+        }
+        "uninstall" => {
+            args.reject_unknown(&["--force", "--scope"])?;
+            let force = Some(args.has("--force"));
+            each(args.some("skill name")?, |name| {
+                let statuses = block_on(uninstall_skill(name.to_string(), force, scope.clone()))?;
+                if statuses.is_empty() {
+                    return Err(format!("Skill '{}' is not installed", name));
+                }
+                Ok(statuses)
+            })
+        }
+        "check-updates" => {
+            args.reject_unknown(&["--no-refresh", "--scope"])?;
+            let targets = scope_targets(scope.as_deref())?;
+            to_json(run_check(None, &targets, !args.has("--no-refresh"))?)
+        }
+        "update" => {
+            args.reject_unknown(&["--scope"])?;
+            let targets = scope_targets(scope.as_deref())?;
+            to_json(apply_updates(None, &targets, None)?)
+        }
+        "favorites" => {
+            args.reject_unknown(&[])?;
+            to_json(block_on(get_favorites())?)
+        }
+        "favorite" => {
+            args.reject_unknown(&[])?;
+            to_json(block_on(toggle_favorite_skill(args.one("skill id")?.to_string()))?)
+        }
+        "favorite-repo" => {
+            args.reject_unknown(&[])?;
+            let (owner, repo) = split_repo(args.one("repo")?)?;
+            to_json(block_on(toggle_favorite_repo(format!("{}/{}", owner, repo)))?)
+        }
+        "custom-repos" => {
+            args.reject_unknown(&[])?;
+            to_json(block_on(get_custom_repos())?)
+        }
+        "add-repo" => {
+            args.reject_unknown(&[])?;
+            let (owner, repo) = split_repo(args.one("repo")?)?;
+            message(block_on(add_custom_repo(owner, repo))?)
+        }
+        "remove-repo" => {
+            args.reject_unknown(&[])?;
+            let (owner, repo) = split_repo(args.one("repo")?)?;
+            message(block_on(remove_custom_repo(owner, repo))?)
+        }
+        "publish" => {
+            args.reject_unknown(&["--pr", "--skills-path", "--branch", "--base", "--message"])?;
+            let [source, remote] = args.positional.as_slice() else {
+                return Err("Expected a skill folder and a repo".to_string());
+            };
+            to_json(publish(&PublishSkillRequest {
+                source: source.clone(),
+                remote: remote.clone(),
+                skills_path: args.value("--skills-path"),
+                branch: args.value("--branch"),
+                base_branch: args.value("--base"),
+                message: args.value("--message"),
+                open_pull_request: args.has("--pr"),
+            })?)
+        }
+        "check-catalog" => {
+            args.reject_unknown(&["--catalog", "--stale-days"])?;
+            let stale_days = match args.value("--stale-days") {
+                Some(days) => days.parse().map_err(|_| format!("Invalid --stale-days '{}'", days))?,
+                None => DEFAULT_STALE_DAYS,
+            };
+            let report = check_catalog(None, &catalog_repos(args.value("--catalog").as_deref())?, stale_days, None);
+            let failed = report.summary.ok < report.summary.total;
+            to_json(report).map(|output| Output { failed, ..output })
+        }
+        other => Err(format!("Unknown command '{}'\n\n{}", other, USAGE)),
+    }
+}
+
+/// Run one CLI command and return the process exit code.
+pub fn run(args: &[String]) -> i32 {
+    let Some((command, rest)) = args.split_first() else {
+        println!("{}", USAGE);
+        return 0;
+    };
+    if matches!(command.as_str(), "help" | "-h" | "--help") {
+        println!("{}", USAGE);
+        return 0;
+    }
+
+    // The desktop app records this at startup; without a window it is found next to the executable
+    if let Some(resource_dir) = find_resource_dir() {
+        set_resource_dir(resource_dir);
+    }
+
+    let result = Args::parse(rest).and_then(|args| execute(command, &args));
+
+    match result {
+        Ok(output) => {
+            // A closed pipe (e.g. `| head`) is not an error for the command itself
+            let _ = writeln!(std::io::stdout(), "{}", output.json);
+            if output.failed {
+                1
+            } else {
+                0
+            }
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(&args.iter().map(|a| a.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn parses_positionals_flags_and_values() {
+        let args = parse(&["a/b/one", "--force", "--scope", "project", "a/b/two", "--scope", "user"]).unwrap();
+        assert_eq!(args.positional, ["a/b/one", "a/b/two"]);
+        assert!(args.has("--force"));
+        assert!(!args.has("--pr"));
+        // The last occurrence of an option wins
+        assert_eq!(args.value("--scope").as_deref(), Some("user"));
+        assert_eq!(args.value("--method"), None);
+    }
+
+    #[test]
+    fn value_option_needs_a_value() {
+        let error = parse(&["skill", "--scope"]).err().unwrap();
+        assert_eq!(error, "--scope needs a value");
+        // A value that looks like a flag is still taken as the value
+        assert_eq!(parse(&["--message", "--pr"]).unwrap().value("--message").as_deref(), Some("--pr"));
+    }
+
+    #[test]
+    fn rejects_options_the_command_does_not_read() {
+        let args = parse(&["name", "--force", "--method", "copy"]).unwrap();
+        assert!(args.reject_unknown(&["--force", "--method"]).is_ok());
+        assert_eq!(args.reject_unknown(&["--force"]).unwrap_err(), "Unknown option '--method'");
+        assert_eq!(args.reject_unknown(&["--method"]).unwrap_err(), "Unknown option '--force'");
+    }
+
+    #[test]
+    fn counts_positionals() {
+        assert_eq!(parse(&["a/b"]).unwrap().one("repo"), Ok("a/b"));
+        assert!(parse(&[]).unwrap().one("repo").is_err());
+        assert!(parse(&["a/b", "c/d"]).unwrap().one("repo").is_err());
+        assert!(parse(&[]).unwrap().some("skill id").is_err());
+        assert_eq!(parse(&["x", "y"]).unwrap().some("skill id").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn splits_owner_and_repo() {
+        assert_eq!(split_repo("owner/repo"), Ok(("owner".to_string(), "repo".to_string())));
+        for value in ["owner", "owner/", "/repo", ""] {
+            assert!(split_repo(value).is_err(), "accepted {:?}", value);
+        }
+    }
+
+    #[test]
+    fn accepts_only_known_install_methods() {
+        assert_eq!(install_method("copy").as_deref(), Ok("copy"));
+        assert_eq!(install_method("native").as_deref(), Ok("native"));
+        for value in ["npx", "Copy", ""] {
+            assert!(install_method(value).is_err(), "accepted {:?}", value);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use super::settings::load_settings;
+use crate::models::AgentTarget;
+use std::path::PathBuf;
+
+pub(crate) const CLAUDE_CODE: &str = "claude-code";
+
+// Agents that load `SKILL.md` folders, with their user (relative to home)
+// and project (relative to the project root) skills directories
+struct AgentSpec {
+    id: &'static str,
+    name: &'static str,
+    user_dir: &'static str,
+    project_dir: &'static str,
+}
+
+const AGENTS: [AgentSpec; 6] = [
+    AgentSpec {
+        id: CLAUDE_CODE,
+        name: "Claude Code",
+        user_dir: ".claude/skills",
+        project_dir: ".claude/skills",
+    },
+    AgentSpec {
+        id: "codex",
+        name: "Codex",
+        user_dir: ".codex/skills",
+        project_dir: ".codex/skills",
+    },
+    AgentSpec {
+        id: "gemini-cli",
+        name: "Gemini CLI",
+        user_dir: ".gemini/skills",
+        project_dir: ".gemini/skills",
+    },
+    AgentSpec {
+        id: "cursor",
+        name: "Cursor",
+        user_dir: ".cursor/skills",
+        project_dir: ".cursor/skills",
+    },
+    AgentSpec {
+        id: "copilot",
+        name: "GitHub Copilot",
+        user_dir: ".copilot/skills",
+        project_dir: ".github/skills",
+    },
+    AgentSpec {
+        id: "opencode",
+        name: "OpenCode",
+        user_dir: ".config/opencode/skills",
+        project_dir: ".opencode/skills",
+    },
+];
+
+/// A concrete skills directory for one agent at one scope.
+#[derive(Debug, Clone)]
+pub(crate) struct InstallTarget {
+    pub agent: &'static str,
+    pub scope: &'static str, // "user" or "project"
+    pub root: PathBuf,
+}
+
+impl InstallTarget {
+    /// Sibling of the skills directory holding disabled skills, which agents do not scan.
+    pub(crate) fn disabled_root(&self) -> PathBuf {
+        self.root.with_file_name("skills-disabled")
+    }
+}
+
+fn home_dir() -> PathBuf {
+    dirs::home_dir().unwrap_or_else(|| PathBuf::from("."))
+}
+
+fn find_agent(id: &str) -> Result<&'static AgentSpec, String> {
+    AGENTS
+        .iter()
+        .find(|a| a.id == id)
+        .ok_or_else(|| format!("Unknown agent '{}'", id))
+}
+
+/// Skills directory of `agent` at `scope` ("user" when None).
+pub(crate) fn agent_target(agent: &str, scope: Option<&str>) -> Result<InstallTarget, String> {
+    let spec = find_agent(agent)?;
+
+    match scope.unwrap_or("user") {
+        "user" => Ok(InstallTarget {
+            agent: spec.id,
+            scope: "user",
+            root: home_dir().join(spec.user_dir),
+        }),
+        "project" => {
+            let project = load_settings()
+                .project_path
+                .ok_or("No project folder configured in settings")?;
+            Ok(InstallTarget {
+                agent: spec.id,
+                scope: "project",
+                root: PathBuf::from(project).join(spec.project_dir),
+            })
+        }
+        other => Err(format!("Unknown install scope '{}'", other)),
+    }
+}
+
+/// Targets for every agent enabled in settings, Claude Code first.
+pub(crate) fn selected_targets(scope: Option<&str>) -> Result<Vec<InstallTarget>, String> {
+    let enabled = load_settings().enabled_agents;
+
+    let targets = AGENTS
+        .iter()
+        .filter(|a| enabled.iter().any(|id| id == a.id))
+        .map(|a| agent_target(a.id, scope))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if targets.is_empty() {
+        return Err("No agents enabled in settings".to_string());
+    }
+    Ok(targets)
+}
+
+/// User-scope targets for every enabled agent, for lookups that must not fail.
+pub(crate) fn user_targets() -> Vec<InstallTarget> {
+    selected_targets(None).unwrap_or_else(|_| {
+        agent_target(CLAUDE_CODE, None).into_iter().collect()
+    })
+}
+
+/// User-scope targets for every known agent, enabled or not.
+pub(crate) fn all_user_targets() -> Vec<InstallTarget> {
+    AGENTS
+        .iter()
+        .filter_map(|a| agent_target(a.id, None).ok())
+        .collect()
+}
+
+#[tauri::command]
+pub async fn get_agent_targets() -> Result<Vec<AgentTarget>, String> {
+    let settings = load_settings();
+
+    Ok(AGENTS
+        .iter()
+        .map(|a| AgentTarget {
+            id: a.id.to_string(),
+            name: a.name.to_string(),
+            user_dir: format!("~/{}", a.user_dir),
+            project_dir: a.project_dir.to_string(),
+            enabled: settings.enabled_agents.iter().any(|id| id == a.id),
+        })
+        .collect())
+}
@@ -0,0 +1,276 @@
+use super::agents::selected_targets;
+use super::install::{copy_skill_dir, install_from_source};
+use super::paths::{get_local_skills_path, unique_staging_path, SkillName};
+use super::skills::{find_skill_file, validate_skill_dir};
+use crate::models::{CreateSkillRequest, CreatedSkill, SkillTemplate};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_TEMPLATE: &str = "basic";
+const MAX_NAME_LEN: usize = 64;
+const MAX_DESCRIPTION_LEN: usize = 1024;
+
+struct BuiltinTemplate {
+    id: &'static str,
+    name: &'static str,
+    description: &'static str,
+    body: &'static str,
+}
+
+// SKILL.md bodies; the frontmatter is generated from the request
+const BUILTIN_TEMPLATES: [BuiltinTemplate; 3] = [
+    BuiltinTemplate {
+        id: "basic",
+        name: "Basic",
+        description: "Instructions only",
+        body: "# {{title}}\n\n{{description}}\n\n## When to use\n\n- \n\n## Instructions\n\n1. \n",
+    },
+    BuiltinTemplate {
+        id: "workflow",
+        name: "Workflow",
+        description: "Step-by-step procedure with a checklist",
+        body: "# {{title}}\n\n{{description}}\n\n## When to use\n\n- \n\n## Steps\n\n1. \n2. \n3. \n\n## Checklist\n\n- [ ] \n",
+    },
+    BuiltinTemplate {
+        id: "tool",
+        name: "Script-backed tool",
+        description: "Instructions that run helper scripts from scripts/",
+        body: "# {{title}}\n\n{{description}}\n\n## Usage\n\nRun the helper script:\n\n```bash\npython scripts/run.py\n```\n\n## Notes\n\n- \n",
+    },
+];
+
+// User-defined templates: folders with a SKILL.md (and any extra files) using the same placeholders
+fn get_templates_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("skill-studio")
+        .join("templates")
+}
+
+fn user_template_dir(id: &str) -> Option<PathBuf> {
+    let dir = get_templates_path().join(SkillName::parse(id).ok()?);
+    find_skill_file(&dir).map(|_| dir)
+}
+
+// Same shape as the Agent Skills spec: lowercase letters, digits and single hyphens
+pub(crate) fn validate_new_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+        && !name.contains("--")
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid skill name '{}': use up to {} lowercase letters, digits and hyphens",
+            name, MAX_NAME_LEN
+        ))
+    }
+}
+
+fn validate_description(description: &str) -> Result<(), String> {
+    if description.trim().is_empty() {
+        return Err("Description cannot be empty".to_string());
+    }
+    if description.len() > MAX_DESCRIPTION_LEN || description.contains('\n') {
+        return Err(format!(
+            "Description must be a single line of at most {} characters",
+            MAX_DESCRIPTION_LEN
+        ));
+    }
+    Ok(())
+}
+
+fn title_case(name: &str) -> String {
+    name.split('-')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn fill_placeholders(text: &str, request: &CreateSkillRequest) -> String {
+    text.replace("{{name}}", &request.name)
+        .replace("{{title}}", &title_case(&request.name))
+        .replace("{{description}}", request.description.trim())
+}
+
+// Double-quoted so descriptions containing ": " or "#" stay valid YAML; parsing the
+// frontmatter undoes the escapes
+fn frontmatter(request: &CreateSkillRequest) -> String {
+    let description = request.description.trim().replace('\\', "\\\\").replace('"', "\\\"");
+    format!("---\nname: {}\ndescription: \"{}\"\n---\n\n", request.name, description)
+}
+
+fn write_file(path: &Path, content: &str, files: &mut Vec<String>) -> Result<(), String> {
+    fs::write(path, content).map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+    files.push(path.to_string_lossy().to_string());
+    Ok(())
+}
+
+// Write the skill folder for `request` into `dest`
+fn render_skill(request: &CreateSkillRequest, dest: &Path) -> Result<Vec<String>, String> {
+    let template = request.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+    fs::create_dir_all(dest).map_err(|e| format!("Failed to create skill folder: {}", e))?;
+    let mut files = Vec::new();
+
+    if let Some(builtin) = BUILTIN_TEMPLATES.iter().find(|t| t.id == template) {
+        let content = frontmatter(request) + &fill_placeholders(builtin.body, request);
+        write_file(&dest.join("SKILL.md"), &content, &mut files)?;
+    } else {
+        let template_dir = user_template_dir(template)
+            .ok_or_else(|| format!("Template '{}' not found", template))?;
+        for path in copy_skill_dir(&template_dir, dest)? {
+            // Placeholders are only filled in text files
+            if let Ok(content) = fs::read_to_string(&path) {
+                write_file(&path, &fill_placeholders(&content, request), &mut files)?;
+            } else {
+                files.push(path.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    for (include, folder) in [(request.include_scripts, "scripts"), (request.include_references, "references")] {
+        if include {
+            let dir = dest.join(folder);
+            fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", folder, e))?;
+            write_file(&dir.join(".gitkeep"), "", &mut files)?;
+        }
+    }
+
+    // Hold new skills to the rules fetched skills go through
+    let parsed = validate_skill_dir(dest)?;
+    if parsed.name != request.name {
+        return Err(format!(
+            "Template produced name '{}' instead of '{}'",
+            parsed.name, request.name
+        ));
+    }
+
+    Ok(files)
+}
+
+#[tauri::command]
+pub async fn list_skill_templates() -> Result<Vec<SkillTemplate>, String> {
+    let mut templates: Vec<SkillTemplate> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|t| SkillTemplate {
+            id: t.id.to_string(),
+            name: t.name.to_string(),
+            description: t.description.to_string(),
+            is_builtin: true,
+        })
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(get_templates_path()) {
+        let mut user_templates: Vec<SkillTemplate> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|id| user_template_dir(id).is_some())
+            .filter(|id| !BUILTIN_TEMPLATES.iter().any(|t| t.id == id))
+            .map(|id| SkillTemplate {
+                name: id.clone(),
+                description: format!("Custom template in {}", get_templates_path().join(&id).display()),
+                id,
+                is_builtin: false,
+            })
+            .collect();
+        user_templates.sort_by(|a, b| a.id.cmp(&b.id));
+        templates.extend(user_templates);
+    }
+
+    Ok(templates)
+}
+
+/// Scaffold a new skill from a template into the local workspace, or straight
+/// into the agent skills directories when `install` is set.
+#[tauri::command]
+pub async fn create_skill(request: CreateSkillRequest) -> Result<CreatedSkill, String> {
+    validate_new_name(&request.name)?;
+    validate_description(&request.description)?;
+    let skill_name = SkillName::parse(&request.name)?;
+
+    if !request.install {
+        let dest = get_local_skills_path().join(&skill_name);
+        if dest.exists() {
+            return Err(format!("A local skill named '{}' already exists", skill_name));
+        }
+        let files = render_skill(&request, &dest).inspect_err(|_| {
+            fs::remove_dir_all(&dest).ok();
+        })?;
+        return Ok(CreatedSkill {
+            name: request.name,
+            paths: vec![dest.to_string_lossy().to_string()],
+            files,
+        });
+    }
+
+    let targets = selected_targets(request.scope.as_deref())?;
+    if let Some(target) = targets.iter().find(|t| t.root.join(&skill_name).exists()) {
+        return Err(format!("Skill '{}' is already installed for {}", skill_name, target.agent));
+    }
+
+    // Render once, then copy into every target like any other install. The staging
+    // folder is unique to this call, so concurrent creates never share one
+    let staging = unique_staging_path("authoring-staging");
+    let source = staging.join(&skill_name);
+    let result = render_skill(&request, &source)
+        .and_then(|_| install_from_source(&source, &skill_name, &targets));
+    fs::remove_dir_all(&staging).ok();
+    let result = result?;
+
+    Ok(CreatedSkill {
+        name: request.name,
+        paths: result.targets.iter().filter(|t| t.success).map(|t| t.path.clone()).collect(),
+        files: result.files_written,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::skills::parse_frontmatter;
+
+    fn request(description: &str) -> CreateSkillRequest {
+        CreateSkillRequest {
+            name: "demo".to_string(),
+            description: description.to_string(),
+            template: None,
+            include_scripts: false,
+            include_references: false,
+            install: false,
+            scope: None,
+        }
+    }
+
+    #[test]
+    fn frontmatter_round_trips_through_the_parser() {
+        for description in [
+            "Plain words",
+            "Use when: the \"quoted\" case applies # not a comment",
+            "Paths like C:\\skills\\demo and a trailing backslash\\",
+            "'Single' quotes at both ends'",
+            "\"",
+        ] {
+            let parsed = parse_frontmatter(&frontmatter(&request(description)));
+            assert_eq!(parsed.name, "demo");
+            assert_eq!(parsed.description, description);
+        }
+    }
+
+    #[test]
+    fn parser_reads_single_quoted_values() {
+        let parsed = parse_frontmatter("---\nname: 'demo'\ndescription: 'It''s fine'\n---\n");
+        assert_eq!(parsed.name, "demo");
+        assert_eq!(parsed.description, "It's fine");
+    }
+}
@@ -0,0 +1,424 @@
+use super::agents::{agent_target, selected_targets, user_targets};
+use super::hashing::hash_dir;
+use super::install::copy_skill_dir;
+use super::paths::{ensure_within, parse_relative_path, resolve_install_path, unique_staging_path, SkillName};
+use super::skills::chrono_now;
+use super::trash::move_to_trash;
+use crate::models::{BackupManifest, BackupSkill, ImportSummary, BACKUP_VERSION};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+// Configuration files worth carrying to another machine (fetched-repos.json
+// describes the local repo cache, which is not part of a backup)
+const CONFIG_FILES: [&str; 4] = ["settings.json", "custom-repos.json", "favorites.json", "bundles.json"];
+
+fn get_config_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("skill-studio")
+}
+
+#[derive(PartialEq)]
+enum ConflictMode {
+    Skip,
+    Overwrite,
+    Rename,
+}
+
+impl ConflictMode {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "skip" => Ok(Self::Skip),
+            "overwrite" => Ok(Self::Overwrite),
+            "rename" => Ok(Self::Rename),
+            other => Err(format!("Unknown conflict mode '{}', expected skip, overwrite or rename", other)),
+        }
+    }
+}
+
+fn append_json<W: std::io::Write>(builder: &mut tar::Builder<W>, path: &str, content: &[u8]) -> std::io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder.append_data(&mut header, path, content)
+}
+
+fn write_backup(file: File) -> Result<BackupManifest, String> {
+    let mut targets = user_targets();
+    if let Ok(project_targets) = selected_targets(Some("project")) {
+        targets.extend(project_targets);
+    }
+
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    let mut manifest = BackupManifest {
+        version: BACKUP_VERSION,
+        created_at: chrono_now(),
+        skills: Vec::new(),
+        config_files: Vec::new(),
+    };
+
+    for target in &targets {
+        for (root, is_disabled) in [(target.root.clone(), false), (target.disabled_root(), true)] {
+            let Ok(entries) = fs::read_dir(&root) else {
+                continue;
+            };
+            for entry in entries.filter_map(|e| e.ok()) {
+                let skill_path = entry.path();
+                if !skill_path.is_dir() {
+                    continue;
+                }
+
+                // Numbered folders keep archive paths valid whatever the skill is called
+                let archive_path = format!("skills/{}", manifest.skills.len());
+                builder
+                    .append_dir_all(&archive_path, &skill_path)
+                    .map_err(|e| format!("Failed to archive {:?}: {}", skill_path, e))?;
+
+                manifest.skills.push(BackupSkill {
+                    folder: entry.file_name().to_string_lossy().to_string(),
+                    agent: target.agent.to_string(),
+                    scope: target.scope.to_string(),
+                    is_disabled,
+                    archive_path,
+                    content_hash: hash_dir(&skill_path)?,
+                });
+            }
+        }
+    }
+
+    let config_path = get_config_path();
+    for name in CONFIG_FILES {
+        if let Ok(content) = fs::read(config_path.join(name)) {
+            append_json(&mut builder, &format!("config/{}", name), &content)
+                .map_err(|e| format!("Failed to archive {}: {}", name, e))?;
+            manifest.config_files.push(name.to_string());
+        }
+    }
+
+    let content = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+    append_json(&mut builder, MANIFEST_FILE, &content).map_err(|e| format!("Failed to write manifest: {}", e))?;
+
+    builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .map_err(|e| format!("Failed to finish archive: {}", e))?;
+
+    Ok(manifest)
+}
+
+/// Write every installed skill (user and project scope, enabled or disabled) and the
+/// app configuration into a `.tar.gz` archive at `path`.
+#[tauri::command]
+pub async fn export_backup(path: String) -> Result<BackupManifest, String> {
+    let file = File::create(&path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    let result = write_backup(file);
+    // A partial archive would look like a usable backup
+    if result.is_err() {
+        fs::remove_file(&path).ok();
+    }
+    result
+}
+
+// Unpack the archive into a fresh staging folder and read its manifest
+fn unpack_backup(path: &str, staging: &Path) -> Result<BackupManifest, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    fs::create_dir_all(staging).map_err(|e| format!("Failed to create staging folder: {}", e))?;
+
+    // `unpack` refuses entries that would land outside the staging folder
+    tar::Archive::new(GzDecoder::new(file))
+        .unpack(staging)
+        .map_err(|e| format!("Failed to read backup archive: {}", e))?;
+
+    let content = fs::read_to_string(staging.join(MANIFEST_FILE))
+        .map_err(|_| "Backup archive has no manifest".to_string())?;
+    let manifest: BackupManifest =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid backup manifest: {}", e))?;
+
+    if manifest.version > BACKUP_VERSION {
+        return Err(format!(
+            "Backup version {} is newer than supported version {}",
+            manifest.version, BACKUP_VERSION
+        ));
+    }
+    Ok(manifest)
+}
+
+// First free "<name>-restored", "<name>-restored-2", ... in `root`
+fn renamed_path(root: &Path, name: &SkillName) -> Result<(SkillName, PathBuf), String> {
+    let mut suffix = 1;
+    loop {
+        let candidate = match suffix {
+            1 => format!("{}-restored", name),
+            n => format!("{}-restored-{}", name, n),
+        };
+        let candidate = SkillName::parse(&candidate)?;
+        let path = resolve_install_path(root, &candidate)?;
+        if path.symlink_metadata().is_err() {
+            return Ok((candidate, path));
+        }
+        suffix += 1;
+    }
+}
+
+fn restore_skill_entry(
+    staging: &Path,
+    skill: &BackupSkill,
+    mode: &ConflictMode,
+    summary: &mut ImportSummary,
+) -> Result<(), String> {
+    let name = SkillName::parse(&skill.folder)?;
+    let target = agent_target(&skill.agent, Some(&skill.scope))?;
+    let source = ensure_within(staging, &staging.join(parse_relative_path(&skill.archive_path)?))?;
+    let actual_hash = hash_dir(&source)?;
+    if actual_hash != skill.content_hash {
+        return Err(format!(
+            "Content hash mismatch (expected {}, got {})",
+            skill.content_hash, actual_hash
+        ));
+    }
+
+    let root = if skill.is_disabled { target.disabled_root() } else { target.root.clone() };
+    let mut dest = resolve_install_path(&root, &name)?;
+    let label = format!("{} ({} {})", name, skill.agent, skill.scope);
+
+    if dest.symlink_metadata().is_ok() {
+        match mode {
+            ConflictMode::Skip => {
+                summary.skipped.push(label);
+                return Ok(());
+            }
+            ConflictMode::Overwrite => {
+                if dest.is_symlink() {
+                    fs::remove_file(&dest).map_err(|e| format!("Failed to remove symlink: {}", e))?;
+                } else {
+                    move_to_trash(&target, &dest, &name)?;
+                }
+            }
+            ConflictMode::Rename => {
+                let (new_name, new_dest) = renamed_path(&root, &name)?;
+                copy_skill_dir(&source, &new_dest)?;
+                summary.renamed.push(format!("{} -> {}", label, new_name));
+                return Ok(());
+            }
+        }
+        dest = resolve_install_path(&root, &name)?;
+    }
+
+    copy_skill_dir(&source, &dest)?;
+    summary.restored.push(label);
+    Ok(())
+}
+
+// Fill in what `local` lacks from `backup`: keys missing from objects, and array items
+// with no equal (or, for objects, same-named) local item. Local values always win.
+fn merge_json(local: &mut serde_json::Value, backup: serde_json::Value) {
+    use serde_json::Value;
+    match (local, backup) {
+        (Value::Object(local), Value::Object(backup)) => {
+            for (key, value) in backup {
+                match local.get_mut(&key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        local.insert(key, value);
+                    }
+                }
+            }
+        }
+        (Value::Array(local), Value::Array(backup)) => {
+            for item in backup {
+                let name = item.get("name").filter(|n| n.is_string());
+                let is_present = local
+                    .iter()
+                    .any(|existing| *existing == item || (name.is_some() && existing.get("name") == name));
+                if !is_present {
+                    local.push(item);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+// The project folder points into this machine's file system, so the local one is kept
+fn keep_project_path(name: &str, local: &serde_json::Value, restored: &mut serde_json::Value) {
+    if name != "settings.json" {
+        return;
+    }
+    if let Some(restored) = restored.as_object_mut() {
+        match local.get("projectPath") {
+            Some(project_path) => restored.insert("projectPath".to_string(), project_path.clone()),
+            None => restored.remove("projectPath"),
+        };
+    }
+}
+
+/// Restore the config file `name` from `backup_dir` into `config_dir`. An existing file
+/// is kept as is (skip), replaced (overwrite) or merged with the backup (rename), where
+/// local values win; settings always keep the local project folder.
+fn restore_config_file(
+    backup_dir: &Path,
+    config_dir: &Path,
+    name: &str,
+    mode: &ConflictMode,
+    summary: &mut ImportSummary,
+) -> Result<(), String> {
+    let backup_file = backup_dir.join(name);
+    let local_file = config_dir.join(name);
+    let read_json = |path: &Path| -> Result<serde_json::Value, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", name, e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Invalid {}: {}", name, e))
+    };
+
+    let mut restored = read_json(&backup_file)?;
+    if local_file.exists() {
+        let mut local = read_json(&local_file)?;
+        match mode {
+            ConflictMode::Skip => {
+                summary.skipped.push(name.to_string());
+                return Ok(());
+            }
+            ConflictMode::Overwrite => keep_project_path(name, &local, &mut restored),
+            ConflictMode::Rename => {
+                merge_json(&mut local, restored);
+                restored = local;
+            }
+        }
+    }
+
+    fs::create_dir_all(config_dir).map_err(|e| format!("Failed to create config folder: {}", e))?;
+    let content = serde_json::to_string_pretty(&restored).map_err(|e| e.to_string())?;
+    fs::write(&local_file, content).map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    summary.config_files.push(name.to_string());
+    Ok(())
+}
+
+/// Restore skills (and, if `restore_config` is set, configuration) from a backup.
+/// Existing skill folders are skipped, overwritten (old copy goes to the trash) or kept
+/// alongside a renamed restored copy depending on `conflict`; existing config files are
+/// skipped, overwritten or merged.
+#[tauri::command]
+pub async fn import_backup(
+    path: String,
+    conflict: String,
+    restore_config: Option<bool>,
+) -> Result<ImportSummary, String> {
+    let mode = ConflictMode::parse(&conflict)?;
+    // Staging folder unique to this import, so concurrent imports never share one
+    let staging = unique_staging_path("backup-staging");
+
+    let result = unpack_backup(&path, &staging).map(|manifest| {
+        let mut summary = ImportSummary::default();
+
+        for skill in &manifest.skills {
+            if let Err(e) = restore_skill_entry(&staging, skill, &mode, &mut summary) {
+                summary.failed.push(format!("{} ({} {}): {}", skill.folder, skill.agent, skill.scope, e));
+            }
+        }
+
+        if restore_config.unwrap_or(false) {
+            let config_path = get_config_path();
+            for name in &manifest.config_files {
+                // Only restore files this version knows about
+                if !CONFIG_FILES.contains(&name.as_str()) {
+                    continue;
+                }
+                let backup_dir = staging.join("config");
+                if let Err(e) = restore_config_file(&backup_dir, &config_path, name, &mode, &mut summary) {
+                    summary.failed.push(format!("{}: {}", name, e));
+                }
+            }
+        }
+
+        summary
+    });
+
+    fs::remove_dir_all(&staging).ok();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::paths::test_dir;
+    use serde_json::{json, Value};
+
+    fn setup(label: &str, name: &str, local: Value, backup: Value) -> (PathBuf, PathBuf) {
+        let dir = test_dir(label);
+        let (backup_dir, config_dir) = (dir.join("backup"), dir.join("config"));
+        for (folder, value) in [(&backup_dir, backup), (&config_dir, local)] {
+            fs::create_dir_all(folder).unwrap();
+            fs::write(folder.join(name), value.to_string()).unwrap();
+        }
+        (backup_dir, config_dir)
+    }
+
+    fn read(dir: &Path, name: &str) -> Value {
+        serde_json::from_str(&fs::read_to_string(dir.join(name)).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn skip_keeps_existing_config() {
+        let local = json!({ "skills": ["a/b/local"], "repos": [] });
+        let (backup_dir, config_dir) = setup("config-skip", "favorites.json", local.clone(), json!({ "skills": [] }));
+        let mut summary = ImportSummary::default();
+
+        restore_config_file(&backup_dir, &config_dir, "favorites.json", &ConflictMode::Skip, &mut summary).unwrap();
+        assert_eq!(read(&config_dir, "favorites.json"), local);
+        assert_eq!(summary.skipped, ["favorites.json"]);
+        assert!(summary.config_files.is_empty());
+    }
+
+    #[test]
+    fn overwrite_keeps_the_local_project_folder() {
+        let local = json!({ "installMethod": "copy", "projectPath": "/home/me/project" });
+        let backup = json!({ "installMethod": "native", "projectPath": "/Users/other/project" });
+        let (backup_dir, config_dir) = setup("config-overwrite", "settings.json", local, backup);
+        let mut summary = ImportSummary::default();
+
+        restore_config_file(&backup_dir, &config_dir, "settings.json", &ConflictMode::Overwrite, &mut summary)
+            .unwrap();
+        assert_eq!(
+            read(&config_dir, "settings.json"),
+            json!({ "installMethod": "native", "projectPath": "/home/me/project" })
+        );
+        assert_eq!(summary.config_files, ["settings.json"]);
+    }
+
+    #[test]
+    fn rename_merges_with_local_values_winning() {
+        let local = json!({ "bundles": [{ "name": "web", "skills": ["local"] }] });
+        let backup = json!({ "bundles": [
+            { "name": "web", "skills": ["backup"] },
+            { "name": "docs", "skills": [] }
+        ] });
+        let (backup_dir, config_dir) = setup("config-merge", "bundles.json", local, backup);
+        let mut summary = ImportSummary::default();
+
+        restore_config_file(&backup_dir, &config_dir, "bundles.json", &ConflictMode::Rename, &mut summary).unwrap();
+        assert_eq!(
+            read(&config_dir, "bundles.json"),
+            json!({ "bundles": [
+                { "name": "web", "skills": ["local"] },
+                { "name": "docs", "skills": [] }
+            ] })
+        );
+    }
+
+    #[test]
+    fn missing_config_is_restored_in_any_mode() {
+        let (backup_dir, config_dir) = setup("config-missing", "favorites.json", json!({}), json!({ "skills": ["x"] }));
+        fs::remove_file(config_dir.join("favorites.json")).unwrap();
+        let mut summary = ImportSummary::default();
+
+        restore_config_file(&backup_dir, &config_dir, "favorites.json", &ConflictMode::Skip, &mut summary).unwrap();
+        assert_eq!(read(&config_dir, "favorites.json"), json!({ "skills": ["x"] }));
+        assert!(summary.skipped.is_empty());
+    }
+}
@@ -0,0 +1,477 @@
+use super::agents::{selected_targets, InstallTarget};
+use super::catalog::load_merged_catalog;
+use super::dependencies::{installed_dependents, missing_dependencies, resolve_dependency};
+use super::install::{install_single, remove_from_targets, replace_installed, SkillLocation};
+use super::paths::{resolve_repo_path, SkillName};
+use super::skills::{cached_skill_dir, collect_all_skills, fetch_repo_to_cache};
+use super::trash::purge_expired_trash;
+use crate::models::{BulkItemResult, BulkProgress, BulkSummary, Skill};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use tauri::Emitter;
+
+const PROGRESS_EVENT: &str = "bulk-progress";
+const DEFAULT_CONCURRENCY: usize = 4;
+const MAX_CONCURRENCY: usize = 8;
+
+/// Progress reporting for one batch, which may run in several phases.
+pub(crate) struct Progress<'a> {
+    pub app: Option<&'a tauri::AppHandle>, // None when running headless
+    pub operation: &'static str,
+    pub total: usize,
+}
+
+impl Progress<'_> {
+    fn emit(&self, item: &str, index: usize, state: &str, error: Option<String>) {
+        let Some(app) = self.app else {
+            return;
+        };
+        let _ = app.emit(
+            PROGRESS_EVENT,
+            BulkProgress {
+                operation: self.operation.to_string(),
+                item: item.to_string(),
+                index,
+                total: self.total,
+                state: state.to_string(),
+                error,
+            },
+        );
+    }
+}
+
+/// Run `job` over `items` on up to `concurrency` worker threads. Every item is attempted;
+/// `offset` positions these items within the overall batch for progress events.
+pub(crate) fn run_batch<F>(
+    progress: &Progress,
+    items: &[String],
+    offset: usize,
+    concurrency: Option<usize>,
+    job: F,
+) -> Vec<BulkItemResult>
+where
+    F: Fn(&str) -> Result<(), String> + Sync,
+{
+    let workers = concurrency
+        .unwrap_or(DEFAULT_CONCURRENCY)
+        .clamp(1, MAX_CONCURRENCY)
+        .min(items.len().max(1));
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<BulkItemResult>>> = Mutex::new(vec![None; items.len()]);
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+
+                progress.emit(item, offset + index, "started", None);
+                let result = job(item);
+                let state = if result.is_ok() { "succeeded" } else { "failed" };
+                progress.emit(item, offset + index, state, result.clone().err());
+
+                if let Ok(mut results) = results.lock() {
+                    results[index] = Some(BulkItemResult {
+                        item: item.clone(),
+                        success: result.is_ok(),
+                        error: result.err(),
+                    });
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+pub(crate) fn summarize(operation: &str, results: Vec<BulkItemResult>) -> BulkSummary {
+    let succeeded = results.iter().filter(|r| r.success).count();
+    BulkSummary {
+        operation: operation.to_string(),
+        total: results.len(),
+        succeeded,
+        failed: results.len() - succeeded,
+        results,
+    }
+}
+
+fn find_skill<'a>(skills: &'a [Skill], id: &str) -> Result<&'a Skill, String> {
+    skills
+        .iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| format!("Skill {} not found", id))
+}
+
+// Folder a skill is installed under: its name, or its repo folder for older installs
+fn installed_folder<'a>(skill: &'a Skill, targets: &[InstallTarget]) -> &'a str {
+    let present = |name: &str| {
+        SkillName::parse(name).is_ok()
+            && targets.iter().any(|t| {
+                t.root.join(name).symlink_metadata().is_ok()
+                    || t.disabled_root().join(name).symlink_metadata().is_ok()
+            })
+    };
+    if !present(&skill.name) && present(&skill.path) {
+        &skill.path
+    } else {
+        &skill.name
+    }
+}
+
+/// Replace installed copies of `skill_ids` with their cached upstream versions.
+pub(crate) fn update_from_cache(
+    progress: &Progress,
+    skills: &[Skill],
+    skill_ids: &[String],
+    targets: &[InstallTarget],
+    concurrency: Option<usize>,
+) -> Vec<BulkItemResult> {
+    run_batch(progress, skill_ids, 0, concurrency, |id| {
+        let skill = find_skill(skills, id)?;
+        let source = cached_skill_dir(skill)?;
+        if !source.exists() {
+            return Err(format!("Skill {} is not in the repo cache", id));
+        }
+        let skill_name = SkillName::parse(installed_folder(skill, targets))?;
+        replace_installed(&source, &skill_name, targets).map(|_| ())
+    })
+}
+
+/// Split `ids` into waves where each skill comes after every skill in `ids` it depends on,
+/// so running the waves in order never installs a skill before its dependencies.
+fn dependency_waves(ids: &[String], skills: &[Skill]) -> Vec<Vec<String>> {
+    fn depth(
+        id: &str,
+        ids: &[String],
+        skills: &[Skill],
+        depths: &mut HashMap<String, usize>,
+        visiting: &mut Vec<String>,
+    ) -> usize {
+        if let Some(&known) = depths.get(id) {
+            return known;
+        }
+        // Dependency cycles cannot be ordered, so the loop is cut where it closes
+        if visiting.iter().any(|v| v == id) {
+            return 0;
+        }
+        let Some(skill) = skills.iter().find(|s| s.id == id) else {
+            return 0;
+        };
+
+        visiting.push(id.to_string());
+        let mut result = 0;
+        for reference in &skill.dependencies {
+            if let Some(dependency) = resolve_dependency(skill, reference, skills) {
+                if ids.contains(&dependency.id) {
+                    result = result.max(depth(&dependency.id, ids, skills, depths, visiting) + 1);
+                }
+            }
+        }
+        visiting.pop();
+        depths.insert(id.to_string(), result);
+        result
+    }
+
+    let mut depths = HashMap::new();
+    let mut waves: Vec<Vec<String>> = Vec::new();
+    for id in ids {
+        let wave = depth(id, ids, skills, &mut depths, &mut Vec::new());
+        if waves.len() <= wave {
+            waves.resize(wave + 1, Vec::new());
+        }
+        waves[wave].push(id.clone());
+    }
+    waves
+}
+
+/// Ids in `ids` whose skill name an earlier id already uses, mapped to the error to report.
+/// Both would be installed into the same folder, so only the first one is attempted.
+fn name_collisions(ids: &[String], skills: &[Skill]) -> HashMap<String, String> {
+    let mut owners: HashMap<&str, &str> = HashMap::new();
+    let mut collisions = HashMap::new();
+    for id in ids {
+        let Some(skill) = skills.iter().find(|s| &s.id == id) else {
+            continue;
+        };
+        match owners.get(skill.name.as_str()) {
+            Some(first) => {
+                collisions.insert(
+                    id.clone(),
+                    format!("Skill name '{}' is also used by {}, which is installed instead", skill.name, first),
+                );
+            }
+            None => {
+                owners.insert(&skill.name, id);
+            }
+        }
+    }
+    collisions
+}
+
+#[tauri::command]
+pub async fn bulk_install(
+    app: tauri::AppHandle,
+    skill_ids: Vec<String>,
+    method: String,
+    scope: Option<String>,
+    concurrency: Option<usize>,
+) -> Result<BulkSummary, String> {
+    let targets = selected_targets(scope.as_deref())?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let skills = collect_all_skills();
+        let catalog = load_merged_catalog().0;
+
+        // Missing dependencies are installed once, before the skills that need them
+        let mut dependencies: Vec<String> = Vec::new();
+        for skill in skills.iter().filter(|s| skill_ids.contains(&s.id)) {
+            for dependency in missing_dependencies(skill, &skills).0 {
+                if !skill_ids.contains(&dependency.id) && !dependencies.contains(&dependency.id) {
+                    dependencies.push(dependency.id);
+                }
+            }
+        }
+
+        // Repos not in the cache yet are fetched once each, before the parallel installs
+        // that would otherwise clone the same repo into the same folder at the same time
+        let mut fetch_errors: HashMap<String, String> = HashMap::new();
+        for skill in skills.iter().filter(|s| dependencies.contains(&s.id) || skill_ids.contains(&s.id)) {
+            let key = format!("{}/{}", skill.owner, skill.repo);
+            let is_cached = resolve_repo_path(&skill.owner, &skill.repo).is_ok_and(|path| path.exists());
+            if is_cached || fetch_errors.contains_key(&key) {
+                continue;
+            }
+            if let Err(e) = fetch_repo_to_cache(&skill.owner, &skill.repo, &catalog) {
+                fetch_errors.insert(key, e);
+            }
+        }
+
+        let mut ids = dependencies;
+        for id in &skill_ids {
+            if !ids.contains(id) {
+                ids.push(id.clone());
+            }
+        }
+        let collisions = name_collisions(&ids, &skills);
+
+        let progress = Progress {
+            app: Some(&app),
+            operation: "install",
+            total: ids.len(),
+        };
+        let job = |id: &str| {
+            if let Some(e) = collisions.get(id) {
+                return Err(e.clone());
+            }
+            let skill = find_skill(&skills, id)?;
+            if let Some(e) = fetch_errors.get(&format!("{}/{}", skill.owner, skill.repo)) {
+                return Err(e.clone());
+            }
+            let skill_name = SkillName::parse(&skill.name)?;
+            install_single(&SkillLocation::of(skill), &skill_name, &method, &targets, &catalog).map(|_| ())
+        };
+
+        let mut results = Vec::new();
+        for wave in dependency_waves(&ids, &skills) {
+            results.extend(run_batch(&progress, &wave, results.len(), concurrency, job));
+        }
+        summarize("install", results)
+    })
+    .await
+    .map_err(|e| format!("Bulk install failed: {}", e))
+}
+
+#[tauri::command]
+pub async fn bulk_uninstall(
+    app: tauri::AppHandle,
+    skill_ids: Vec<String>,
+    force: Option<bool>,
+    scope: Option<String>,
+    concurrency: Option<usize>,
+) -> Result<BulkSummary, String> {
+    let targets = selected_targets(scope.as_deref())?;
+    purge_expired_trash();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let skills = collect_all_skills();
+        // Skills removed together may depend on each other
+        let removed_names: Vec<&str> = skills
+            .iter()
+            .filter(|s| skill_ids.contains(&s.id))
+            .map(|s| s.name.as_str())
+            .collect();
+
+        let progress = Progress {
+            app: Some(&app),
+            operation: "uninstall",
+            total: skill_ids.len(),
+        };
+        let results = run_batch(&progress, &skill_ids, 0, concurrency, |id| {
+            let skill = find_skill(&skills, id)?;
+
+            if !force.unwrap_or(false) {
+                let dependents: Vec<String> = installed_dependents(&skill.name, &skills)
+                    .into_iter()
+                    .filter(|d| !removed_names.contains(&d.as_str()))
+                    .collect();
+                if !dependents.is_empty() {
+                    return Err(format!("Required by {}", dependents.join(", ")));
+                }
+            }
+
+            let skill_name = SkillName::parse(installed_folder(skill, &targets))?;
+            let statuses = remove_from_targets(&skill_name, &targets);
+            if statuses.is_empty() {
+                return Err(format!("Skill '{}' is not installed", skill_name));
+            }
+            match statuses.into_iter().find_map(|s| s.error) {
+                Some(error) => Err(error),
+                None => Ok(()),
+            }
+        });
+        summarize("uninstall", results)
+    })
+    .await
+    .map_err(|e| format!("Bulk uninstall failed: {}", e))
+}
+
+/// Refresh the repos of `skill_ids`, then replace each installed copy with the latest version.
+#[tauri::command]
+pub async fn bulk_update(
+    app: tauri::AppHandle,
+    skill_ids: Vec<String>,
+    scope: Option<String>,
+    concurrency: Option<usize>,
+) -> Result<BulkSummary, String> {
+    let targets = selected_targets(scope.as_deref())?;
+    purge_expired_trash();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let skills = collect_all_skills();
+
+        let mut repos: Vec<String> = skills
+            .iter()
+            .filter(|s| skill_ids.contains(&s.id))
+            .map(|s| format!("{}/{}", s.owner, s.repo))
+            .collect();
+        repos.sort();
+        repos.dedup();
+        let catalog = load_merged_catalog().0;
+
+        let refresh = Progress {
+            app: Some(&app),
+            operation: "refresh",
+            total: repos.len(),
+        };
+        let refreshed = run_batch(&refresh, &repos, 0, concurrency, |key| {
+            let (owner, repo) = key.split_once('/').ok_or("Invalid repo")?;
+            fetch_repo_to_cache(owner, repo, &catalog).map(|_| ())
+        });
+
+        // Skills whose repo could not be refreshed are reported as failed, not updated
+        let mut results = Vec::new();
+        let mut to_update = Vec::new();
+        for id in &skill_ids {
+            let failed_refresh = skills
+                .iter()
+                .find(|s| &s.id == id)
+                .and_then(|s| {
+                    let key = format!("{}/{}", s.owner, s.repo);
+                    refreshed.iter().find(|r| r.item == key && !r.success)
+                });
+            match failed_refresh {
+                Some(refresh) => results.push(BulkItemResult {
+                    item: id.clone(),
+                    success: false,
+                    error: refresh.error.clone(),
+                }),
+                None => to_update.push(id.clone()),
+            }
+        }
+
+        let skills = collect_all_skills();
+        let progress = Progress {
+            app: Some(&app),
+            operation: "update",
+            total: to_update.len(),
+        };
+        results.extend(update_from_cache(&progress, &skills, &to_update, &targets, concurrency));
+        summarize("update", results)
+    })
+    .await
+    .map_err(|e| format!("Bulk update failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill(id: &str, name: &str, dependencies: &[&str]) -> Skill {
+        let parts: Vec<&str> = id.split('/').collect();
+        Skill {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            owner: parts[0].to_string(),
+            repo: parts[1].to_string(),
+            skills_path: "skills".to_string(),
+            path: parts[2].to_string(),
+            content: None,
+            is_installed: false,
+            is_fetched: true,
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            is_disabled: false,
+            installs: None,
+            recent_installs: None,
+        }
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn waves_put_dependencies_first() {
+        let skills = [
+            skill("o/r/app", "app", &["lib", "o/r/tool"]),
+            skill("o/r/lib", "lib", &["base"]),
+            skill("o/r/base", "base", &[]),
+            skill("o/r/tool", "tool", &[]),
+            skill("o/r/other", "other", &["unselected"]),
+            skill("o/r/unselected", "unselected", &[]),
+        ];
+        let waves = dependency_waves(&ids(&["o/r/app", "o/r/lib", "o/r/base", "o/r/tool", "o/r/other"]), &skills);
+        assert_eq!(
+            waves,
+            [ids(&["o/r/base", "o/r/tool", "o/r/other"]), ids(&["o/r/lib"]), ids(&["o/r/app"])]
+        );
+    }
+
+    #[test]
+    fn waves_survive_cycles() {
+        let skills = [skill("o/r/a", "a", &["b"]), skill("o/r/b", "b", &["a"])];
+        let waves = dependency_waves(&ids(&["o/r/a", "o/r/b"]), &skills);
+        assert_eq!(waves.concat().len(), 2);
+    }
+
+    #[test]
+    fn same_named_skills_collide() {
+        let skills = [
+            skill("a/one/pdf", "pdf", &[]),
+            skill("b/two/pdf", "pdf", &[]),
+            skill("b/two/docs", "docs", &[]),
+        ];
+        let collisions = name_collisions(&ids(&["a/one/pdf", "b/two/docs", "b/two/pdf"]), &skills);
+        assert_eq!(collisions.len(), 1);
+        assert!(collisions["b/two/pdf"].contains("a/one/pdf"));
+    }
+}
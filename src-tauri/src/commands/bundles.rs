@@ -0,0 +1,466 @@
+use super::agents::{selected_targets, user_targets};
+use super::catalog::{load_merged_catalog, MergedRepo};
+use super::dependencies::{installed_dependents, missing_dependencies};
+use super::git::{github_url, is_immutable_ref, shallow_clone, validate_git_ref};
+use super::hashing::hash_dir;
+use super::install::{install_from_source, install_single, remove_from_targets, SkillLocation};
+use super::paths::{
+    parse_relative_path, resolve_pinned_repo_path, resolve_repo_path, unique_staging_path, SkillName,
+};
+use super::skills::{
+    collect_all_skills, fetch_repo_to_cache, installed_skill_dir, list_installed_skill_names,
+    load_catalog, scan_skills_in,
+};
+use crate::models::{Bundle, BundleMemberState, BundleMemberStatus, BundleSkill, Skill};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn get_bundles_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("skill-studio")
+        .join("bundles.json")
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Default)]
+struct LocalBundles {
+    bundles: Vec<Bundle>,
+}
+
+fn load_local_bundles() -> LocalBundles {
+    let path = get_bundles_path();
+    if path.exists() {
+        fs::read_to_string(&path)
+            .ok()
+            .and_then(|c| serde_json::from_str(&c).ok())
+            .unwrap_or_default()
+    } else {
+        LocalBundles::default()
+    }
+}
+
+fn save_local_bundles(bundles: &LocalBundles) -> Result<(), String> {
+    let path = get_bundles_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(bundles).map_err(|e| e.to_string())?;
+    fs::write(&path, content).map_err(|e| e.to_string())
+}
+
+// Local bundles first, then catalog bundles whose name is not taken locally
+fn load_all_bundles() -> Vec<Bundle> {
+    let mut bundles = load_local_bundles().bundles;
+
+    if let Ok(catalog) = load_catalog() {
+        for mut bundle in catalog.bundles {
+            if !bundles.iter().any(|b| b.name == bundle.name) {
+                bundle.is_catalog = true;
+                bundles.push(bundle);
+            }
+        }
+    }
+
+    bundles
+}
+
+fn find_bundle(name: &str) -> Result<Bundle, String> {
+    load_all_bundles()
+        .into_iter()
+        .find(|b| b.name == name)
+        .ok_or_else(|| format!("Bundle '{}' not found", name))
+}
+
+fn split_skill_id(id: &str) -> Result<(&str, &str, &str), String> {
+    let mut parts = id.splitn(3, '/');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(owner), Some(repo), Some(skill)) if !skill.is_empty() => Ok((owner, repo, skill)),
+        _ => Err(format!("Invalid skill id '{}', expected owner/repo/skill", id)),
+    }
+}
+
+fn validate_bundle(bundle: &Bundle) -> Result<(), String> {
+    if bundle.name.trim().is_empty() {
+        return Err("Bundle name cannot be empty".to_string());
+    }
+    for member in &bundle.skills {
+        let (owner, repo, _) = split_skill_id(&member.id)?;
+        resolve_repo_path(owner, repo)?;
+        if let Some(ref git_ref) = member.git_ref {
+            validate_git_ref(git_ref)?;
+        }
+    }
+    Ok(())
+}
+
+/// Bring `pinned_path` to `url` at `git_ref`. Checkouts of commit SHAs and tags are kept
+/// once fetched; branches are cloned again into `staging` and swapped in, since they move.
+fn refresh_pinned(url: &str, pinned_path: &Path, git_ref: &str, staging: &Path) -> Result<(), String> {
+    if pinned_path.exists() && is_immutable_ref(url, git_ref)? {
+        return Ok(());
+    }
+
+    fs::remove_dir_all(staging).ok();
+    if let Err(e) = shallow_clone(url, staging, Some(git_ref)) {
+        fs::remove_dir_all(staging).ok();
+        return Err(e);
+    }
+    if pinned_path.exists() {
+        fs::remove_dir_all(pinned_path).map_err(|e| format!("Failed to remove old checkout: {}", e))?;
+    }
+    if let Some(parent) = pinned_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create dir: {}", e))?;
+    }
+    if let Err(e) = fs::rename(staging, pinned_path) {
+        fs::remove_dir_all(staging).ok();
+        // Another install refreshed the same checkout first
+        if !pinned_path.exists() {
+            return Err(format!("Failed to move checkout into place: {}", e));
+        }
+    }
+    Ok(())
+}
+
+// Root skills are named after their frontmatter, not their folder, so match on the full id
+fn member_skill(skills: Vec<Skill>, id: &str) -> Option<Skill> {
+    skills.into_iter().find(|s| s.id == id)
+}
+
+/// Locate a bundle member and its source folder. Members use the repo cache (or the
+/// checkout of their pinned ref), which is only fetched when `fetch` is set.
+pub(crate) fn resolve_member(
+    member: &BundleSkill,
+    skills: &[Skill],
+    installed: &[String],
+    catalog: &[MergedRepo],
+    fetch: bool,
+) -> Result<(Skill, PathBuf), String> {
+    let (owner, repo, _) = split_skill_id(&member.id)?;
+
+    let (skill, repo_path) = match member.git_ref {
+        Some(ref git_ref) => {
+            validate_git_ref(git_ref)?;
+            let pinned_path = resolve_pinned_repo_path(owner, repo, git_ref)?;
+            if fetch {
+                let staging = unique_staging_path("pinned-staging");
+                refresh_pinned(&github_url(owner, repo), &pinned_path, git_ref, &staging)?;
+            } else if !pinned_path.exists() {
+                return Err(format!("{} at {} is not fetched", member.id, git_ref));
+            }
+            let skill = member_skill(scan_skills_in(&pinned_path, owner, repo, installed, catalog), &member.id);
+            (skill, pinned_path)
+        }
+        None => {
+            // Skills only known from the index have nothing in the cache yet
+            let mut skill = skills.iter().find(|s| s.id == member.id && s.is_fetched).cloned();
+            if skill.is_none() && fetch {
+                skill = member_skill(fetch_repo_to_cache(owner, repo, catalog)?, &member.id);
+            }
+            (skill, resolve_repo_path(owner, repo)?)
+        }
+    };
+
+    let skill = skill.ok_or_else(|| format!("Skill {} not found", member.id))?;
+    let source = repo_path
+        .join(parse_relative_path(&skill.skills_path)?)
+        .join(parse_relative_path(&skill.path)?);
+    Ok((skill, source))
+}
+
+fn member_status(
+    member: &BundleSkill,
+    skills: &[Skill],
+    installed: &[String],
+    catalog: &[MergedRepo],
+) -> BundleMemberStatus {
+    let mut status = BundleMemberStatus {
+        id: member.id.clone(),
+        git_ref: member.git_ref.clone(),
+        name: None,
+        state: BundleMemberState::Unavailable,
+        message: None,
+    };
+
+    let (skill, source) = match resolve_member(member, skills, installed, catalog, false) {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            status.message = Some(e);
+            return status;
+        }
+    };
+    status.name = Some(skill.name.clone());
+
+    let Some(installed_dir) = installed_skill_dir(&skill) else {
+        status.state = BundleMemberState::Missing;
+        return status;
+    };
+    status.state = match hash_dir(&installed_dir).and_then(|installed| Ok((installed, hash_dir(&source)?))) {
+        Ok((installed_hash, source_hash)) if installed_hash == source_hash => BundleMemberState::Installed,
+        Ok(_) => BundleMemberState::Outdated,
+        Err(e) => {
+            status.message = Some(e);
+            BundleMemberState::Error
+        }
+    };
+    status
+}
+
+#[tauri::command]
+pub async fn get_bundles() -> Result<Vec<Bundle>, String> {
+    Ok(load_all_bundles())
+}
+
+#[tauri::command]
+pub async fn save_bundle(bundle: Bundle) -> Result<Bundle, String> {
+    validate_bundle(&bundle)?;
+
+    let mut local = load_local_bundles();
+    let bundle = Bundle {
+        is_catalog: false,
+        ..bundle
+    };
+
+    match local.bundles.iter_mut().find(|b| b.name == bundle.name) {
+        Some(existing) => *existing = bundle.clone(),
+        None => local.bundles.push(bundle.clone()),
+    }
+    save_local_bundles(&local)?;
+
+    Ok(bundle)
+}
+
+#[tauri::command]
+pub async fn delete_bundle(name: String) -> Result<(), String> {
+    let mut local = load_local_bundles();
+    let initial_len = local.bundles.len();
+    local.bundles.retain(|b| b.name != name);
+
+    if local.bundles.len() == initial_len {
+        return Err(format!("Local bundle '{}' not found", name));
+    }
+
+    save_local_bundles(&local)
+}
+
+#[tauri::command]
+pub async fn get_bundle_status(
+    name: String,
+) -> Result<Vec<BundleMemberStatus>, String> {
+    let bundle = find_bundle(&name)?;
+    let skills = collect_all_skills();
+    let installed = list_installed_skill_names();
+    let catalog = load_merged_catalog().0;
+
+    Ok(bundle
+        .skills
+        .iter()
+        .map(|member| member_status(member, &skills, &installed, &catalog))
+        .collect())
+}
+
+#[tauri::command]
+pub async fn install_bundle(
+    name: String,
+    method: String,
+) -> Result<String, String> {
+    let bundle = find_bundle(&name)?;
+    let skills = collect_all_skills();
+    let installed = list_installed_skill_names();
+    let targets = selected_targets(None)?;
+    let catalog = load_merged_catalog().0;
+
+    let mut count = 0;
+    let mut failures = Vec::new();
+    let mut warnings = Vec::new();
+    let mut dependencies_installed: Vec<String> = Vec::new();
+
+    for member in &bundle.skills {
+        let resolved = resolve_member(member, &skills, &installed, &catalog, true);
+
+        // Dependencies go in first, once each, so members work right away
+        if let Ok((ref skill, _)) = resolved {
+            let (dependencies, unresolved) = missing_dependencies(skill, &skills);
+            warnings.extend(unresolved);
+            for dependency in dependencies {
+                let is_member = bundle.skills.iter().any(|m| m.id == dependency.id);
+                if is_member || dependencies_installed.contains(&dependency.id) {
+                    continue;
+                }
+                let result = SkillName::parse(&dependency.name).and_then(|n| {
+                    install_single(&SkillLocation::of(&dependency), &n, &method, &targets, &catalog)
+                });
+                match result {
+                    Ok(_) => dependencies_installed.push(dependency.id),
+                    Err(e) => failures.push(format!("{} (dependency of {}): {}", dependency.id, member.id, e)),
+                }
+            }
+        }
+
+        let result = match resolved {
+            // Pinned revisions can only be installed by copying the checkout
+            Ok((skill, source)) if member.git_ref.is_some() || method == "copy" => {
+                SkillName::parse(&skill.name)
+                    .and_then(|n| install_from_source(&source, &n, &targets))
+            }
+            Ok((skill, _)) => match SkillName::parse(&skill.name) {
+                Ok(skill_name) => install_single(&SkillLocation::of(&skill), &skill_name, &method, &targets, &catalog),
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(_) => count += 1,
+            Err(e) => failures.push(format!("{}: {}", member.id, e)),
+        }
+    }
+
+    let mut extra = String::new();
+    if !dependencies_installed.is_empty() {
+        extra.push_str(&format!(" and {} dependencies", dependencies_installed.len()));
+    }
+    if !warnings.is_empty() {
+        extra.push_str(&format!(". Warnings: {}", warnings.join("; ")));
+    }
+
+    if failures.is_empty() {
+        Ok(format!("Installed {} skills from bundle '{}'{}", count, bundle.name, extra))
+    } else {
+        Err(format!(
+            "Installed {} of {} skills from bundle '{}'{}. Failed: {}",
+            count,
+            bundle.skills.len(),
+            bundle.name,
+            extra,
+            failures.join("; ")
+        ))
+    }
+}
+
+#[tauri::command]
+pub async fn uninstall_bundle(name: String, force: Option<bool>) -> Result<(), String> {
+    let bundle = find_bundle(&name)?;
+    let skills = collect_all_skills();
+    let targets = user_targets();
+
+    let mut names = Vec::new();
+    for member in &bundle.skills {
+        let (_, _, folder) = split_skill_id(&member.id)?;
+        let skill_name = skills
+            .iter()
+            .find(|s| s.id == member.id)
+            .map(|s| s.name.as_str())
+            .unwrap_or(folder);
+        names.push(skill_name);
+    }
+
+    // Skills outside the bundle that still need a member keep the whole bundle installed
+    if !force.unwrap_or(false) {
+        for skill_name in &names {
+            let dependents: Vec<String> = installed_dependents(skill_name, &skills)
+                .into_iter()
+                .filter(|d| !names.contains(&d.as_str()))
+                .collect();
+            if !dependents.is_empty() {
+                return Err(format!(
+                    "Skill '{}' is required by {}",
+                    skill_name,
+                    dependents.join(", ")
+                ));
+            }
+        }
+    }
+
+    for skill_name in names {
+        let statuses = remove_from_targets(&SkillName::parse(skill_name)?, &targets);
+        if let Some(error) = statuses.into_iter().find_map(|s| s.error) {
+            return Err(error);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::git::{test_git, test_push, test_remote};
+    use crate::commands::paths::test_dir;
+    use crate::commands::skills::scan_repo_dir;
+
+    fn write_skill(dir: &Path, description: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("SKILL.md"), format!("---\nname: demo\ndescription: {}\n---\n", description)).unwrap();
+    }
+
+    fn description(checkout: &Path) -> String {
+        fs::read_to_string(checkout.join("skills/demo/SKILL.md")).unwrap()
+    }
+
+    #[test]
+    fn branch_checkouts_are_refreshed() {
+        let dir = test_dir("pinned-branch");
+        let (remote, work) = test_remote(&dir);
+        let url = remote.to_str().unwrap();
+        write_skill(&work.join("skills/demo"), "first");
+        test_push(&work, "First");
+        let branch = test_git(&["rev-parse", "--abbrev-ref", "HEAD"], &work);
+
+        let pinned = dir.join("pinned");
+        refresh_pinned(url, &pinned, &branch, &dir.join("staging")).unwrap();
+        assert!(description(&pinned).contains("first"));
+
+        write_skill(&work.join("skills/demo"), "second");
+        test_push(&work, "Second");
+        refresh_pinned(url, &pinned, &branch, &dir.join("staging")).unwrap();
+        assert!(description(&pinned).contains("second"));
+        assert!(!dir.join("staging").exists());
+    }
+
+    #[test]
+    fn tag_and_sha_checkouts_are_kept() {
+        let dir = test_dir("pinned-tag");
+        let (remote, work) = test_remote(&dir);
+        let url = remote.to_str().unwrap();
+        write_skill(&work.join("skills/demo"), "first");
+        let sha = test_push(&work, "First");
+        test_git(&["tag", "v1"], &work);
+        test_git(&["push", "--quiet", "origin", "v1"], &work);
+
+        for (git_ref, pinned) in [("v1", dir.join("by-tag")), (sha.as_str(), dir.join("by-sha"))] {
+            refresh_pinned(url, &pinned, git_ref, &dir.join("staging")).unwrap();
+            assert!(description(&pinned).contains("first"));
+
+            // A second refresh leaves the existing checkout alone
+            fs::write(pinned.join("marker"), "").unwrap();
+            refresh_pinned(url, &pinned, git_ref, &dir.join("staging")).unwrap();
+            assert!(pinned.join("marker").exists(), "{} was fetched again", git_ref);
+        }
+    }
+
+    #[test]
+    fn failed_refresh_keeps_the_old_checkout() {
+        let dir = test_dir("pinned-missing");
+        let (remote, work) = test_remote(&dir);
+        write_skill(&work.join("skills/demo"), "first");
+        test_push(&work, "First");
+
+        let pinned = dir.join("pinned");
+        write_skill(&pinned.join("skills/demo"), "old");
+        let result = refresh_pinned(remote.to_str().unwrap(), &pinned, "no-such-branch", &dir.join("staging"));
+        assert!(result.is_err());
+        assert!(description(&pinned).contains("old"));
+        assert!(!dir.join("staging").exists());
+    }
+
+    #[test]
+    fn root_skills_match_on_their_id() {
+        let dir = test_dir("bundle-root-skill");
+        write_skill(&dir, "root");
+        let skills = scan_repo_dir(&dir, "owner", "single", None, &[]);
+        assert_eq!(skills[0].path, ".");
+
+        let skill = member_skill(skills, "owner/single/demo").unwrap();
+        assert_eq!(skill.name, "demo");
+    }
+}
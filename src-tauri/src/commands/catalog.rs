@@ -0,0 +1,551 @@
+use super::hashing::hash_key;
+use super::paths::{get_data_path, SkillName};
+use super::settings::load_settings;
+use super::skills::{chrono_now, get_library_path};
+use crate::models::{Bundle, Catalog, CatalogInfo, CatalogRepo, CatalogSource, Settings, SkillIndex};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+// Catalogs with a different major version use a schema this build cannot read
+const SUPPORTED_MAJOR_VERSION: &str = "1";
+const FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// A downloaded catalog: the official update or a remote source.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedCatalog {
+    url: String,
+    fetched_at: String,
+    catalog: Catalog,
+}
+
+/// A downloaded skill index, checked against the bundled one like the catalog.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedIndex {
+    url: String,
+    fetched_at: String,
+    index: SkillIndex,
+}
+
+fn get_cached_catalog_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("remote-catalog.json")
+}
+
+fn get_cached_index_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("remote-index.json")
+}
+
+// Downloaded copies of remote catalog sources, keyed by URL
+fn get_source_cache_path(data_dir: &Path, url: &str) -> PathBuf {
+    data_dir.join("catalogs").join(format!("{}.json", hash_key(url)))
+}
+
+pub(crate) fn validate_catalog(catalog: &Catalog) -> Result<(), String> {
+    let major = catalog.version.split('.').next().unwrap_or_default();
+    if major != SUPPORTED_MAJOR_VERSION {
+        return Err(format!(
+            "Unsupported catalog version {} (expected {}.x)",
+            catalog.version, SUPPORTED_MAJOR_VERSION
+        ));
+    }
+
+    for repo in &catalog.repos {
+        let valid = repo
+            .url
+            .split_once('/')
+            .is_some_and(|(owner, name)| SkillName::parse(owner).is_ok() && SkillName::parse(name).is_ok());
+        if !valid {
+            return Err(format!("Invalid catalog repo '{}', expected owner/repo", repo.url));
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn parse_catalog(content: &str) -> Result<Catalog, String> {
+    let catalog: Catalog =
+        serde_json::from_str(content).map_err(|e| format!("Failed to parse catalog: {}", e))?;
+    validate_catalog(&catalog)?;
+    Ok(catalog)
+}
+
+fn load_bundled_catalog(library: &Path) -> Result<Catalog, String> {
+    let content = fs::read_to_string(library.join("catalog.json"))
+        .map_err(|e| format!("Failed to read catalog: {}", e))?;
+    parse_catalog(&content)
+}
+
+fn load_cached_catalog(data_dir: &Path) -> Option<CachedCatalog> {
+    let cached: CachedCatalog = fs::read_to_string(get_cached_catalog_path(data_dir))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())?;
+    validate_catalog(&cached.catalog).ok()?;
+    Some(cached)
+}
+
+/// The catalog in use: the downloaded copy unless the bundled one is newer
+/// (e.g. after an app update) or nothing valid has been downloaded.
+pub(crate) fn load_active_catalog() -> Result<(Catalog, CatalogInfo), String> {
+    active_catalog(&get_library_path(), &get_data_path())
+}
+
+/// `lastUpdated` as [year, month, day, hour, minute, second], so "2026-10-01" sorts after
+/// "2026-9-30" and a timestamp compares with a plain date. None when it is not a date.
+fn parse_last_updated(value: &str) -> Option<[u32; 6]> {
+    let value = value.trim().trim_end_matches('Z');
+    let (date, time) = value.split_once(['T', ' ']).unwrap_or((value, ""));
+
+    let mut parts = [0; 6];
+    let date: Vec<&str> = date.split('-').collect();
+    let time: Vec<&str> = if time.is_empty() { Vec::new() } else { time.split(':').collect() };
+    if date.len() != 3 || time.len() > 3 {
+        return None;
+    }
+    for (part, text) in parts.iter_mut().zip(date.into_iter().chain(time)) {
+        // Fractional seconds do not change the order of catalogs
+        *part = text.split('.').next()?.parse().ok()?;
+    }
+
+    let [_, month, day, hour, minute, second] = parts;
+    let valid = (1..=12).contains(&month) && (1..=31).contains(&day) && hour < 24 && minute < 60 && second < 61;
+    valid.then_some(parts)
+}
+
+fn active_catalog(library: &Path, data_dir: &Path) -> Result<(Catalog, CatalogInfo), String> {
+    let bundled = load_bundled_catalog(library);
+    let cached = load_cached_catalog(data_dir);
+
+    let use_cached = match (&cached, &bundled) {
+        (Some(cached), Ok(bundled)) => {
+            parse_last_updated(&cached.catalog.last_updated) >= parse_last_updated(&bundled.last_updated)
+        }
+        (Some(_), Err(_)) => true,
+        (None, _) => false,
+    };
+
+    if let (true, Some(cached)) = (use_cached, cached) {
+        let info = catalog_info(&cached.catalog, "remote", Some(cached.url), Some(cached.fetched_at));
+        return Ok((cached.catalog, info));
+    }
+
+    let catalog = bundled?;
+    let info = catalog_info(&catalog, "bundled", None, None);
+    Ok((catalog, info))
+}
+
+fn validate_index(index: &SkillIndex) -> Result<(), String> {
+    let major = index.version.split('.').next().unwrap_or_default();
+    if major != SUPPORTED_MAJOR_VERSION {
+        return Err(format!(
+            "Unsupported skill index version {} (expected {}.x)",
+            index.version, SUPPORTED_MAJOR_VERSION
+        ));
+    }
+    Ok(())
+}
+
+fn parse_index(content: &str) -> Result<SkillIndex, String> {
+    let index: SkillIndex =
+        serde_json::from_str(content).map_err(|e| format!("Failed to parse skill index: {}", e))?;
+    validate_index(&index)?;
+    Ok(index)
+}
+
+/// The skill index in use, picked like the catalog: the downloaded copy unless
+/// the bundled one is newer. None when neither exists, e.g. in a dev checkout.
+pub(crate) fn load_skill_index() -> Option<SkillIndex> {
+    let bundled = fs::read_to_string(get_library_path().join("index.json"))
+        .ok()
+        .and_then(|c| parse_index(&c).ok());
+    let cached = fs::read_to_string(get_cached_index_path(&get_data_path()))
+        .ok()
+        .and_then(|c| serde_json::from_str::<CachedIndex>(&c).ok())
+        .filter(|c| validate_index(&c.index).is_ok())
+        .map(|c| c.index);
+
+    match (cached, bundled) {
+        (Some(cached), Some(bundled))
+            if parse_last_updated(&bundled.last_updated) > parse_last_updated(&cached.last_updated) =>
+        {
+            Some(bundled)
+        }
+        (Some(cached), _) => Some(cached),
+        (None, bundled) => bundled,
+    }
+}
+
+fn catalog_info(catalog: &Catalog, source: &str, url: Option<String>, fetched_at: Option<String>) -> CatalogInfo {
+    CatalogInfo {
+        name: String::new(),
+        source: source.to_string(),
+        version: catalog.version.clone(),
+        last_updated: catalog.last_updated.clone(),
+        url,
+        fetched_at,
+        repo_count: catalog.repos.len(),
+        error: None,
+    }
+}
+
+fn source_location(source: &CatalogSource) -> Result<&str, String> {
+    source
+        .location
+        .as_deref()
+        .filter(|l| !l.trim().is_empty())
+        .ok_or_else(|| format!("Catalog '{}' has no location", source.name))
+}
+
+fn load_source(source: &CatalogSource) -> Result<(Catalog, CatalogInfo), String> {
+    let (catalog, mut info) = match source.kind.as_str() {
+        "bundled" => load_active_catalog()?,
+        "remote" => {
+            let url = source_location(source)?;
+            let cached: CachedCatalog = fs::read_to_string(get_source_cache_path(&get_data_path(), url))
+                .ok()
+                .and_then(|c| serde_json::from_str(&c).ok())
+                .ok_or_else(|| format!("Catalog '{}' has not been downloaded yet", source.name))?;
+            validate_catalog(&cached.catalog)?;
+            let info = catalog_info(&cached.catalog, "remote", Some(cached.url), Some(cached.fetched_at));
+            (cached.catalog, info)
+        }
+        "file" => {
+            let path = source_location(source)?;
+            let content = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read catalog {}: {}", path, e))?;
+            let catalog = parse_catalog(&content)?;
+            let info = catalog_info(&catalog, "file", Some(path.to_string()), None);
+            (catalog, info)
+        }
+        other => return Err(format!("Unknown catalog source kind '{}'", other)),
+    };
+
+    info.name = source.name.clone();
+    Ok((catalog, info))
+}
+
+fn enabled_sources() -> Vec<CatalogSource> {
+    load_settings().catalog_sources.into_iter().filter(|s| s.enabled).collect()
+}
+
+/// A catalog repo with the names of every source listing it.
+pub(crate) struct MergedRepo {
+    pub repo: CatalogRepo,
+    pub catalogs: Vec<String>,
+}
+
+/// Repos and bundles from every enabled source. Sources earlier in settings take
+/// precedence: their entry is kept when several catalogs list the same repo or bundle.
+pub(crate) fn load_merged_catalog() -> (Vec<MergedRepo>, Vec<Bundle>) {
+    let mut repos: Vec<MergedRepo> = Vec::new();
+    let mut bundles: Vec<Bundle> = Vec::new();
+
+    for source in enabled_sources() {
+        let Ok((catalog, _)) = load_source(&source) else {
+            continue;
+        };
+
+        for repo in catalog.repos {
+            match repos.iter_mut().find(|r| r.repo.url.eq_ignore_ascii_case(&repo.url)) {
+                Some(existing) => {
+                    if !existing.catalogs.contains(&source.name) {
+                        existing.catalogs.push(source.name.clone());
+                    }
+                }
+                None => repos.push(MergedRepo {
+                    repo,
+                    catalogs: vec![source.name.clone()],
+                }),
+            }
+        }
+
+        for bundle in catalog.bundles {
+            if !bundles.iter().any(|b| b.name == bundle.name) {
+                bundles.push(bundle);
+            }
+        }
+    }
+
+    (repos, bundles)
+}
+
+/// Skills folder a catalog declares for `owner/repo`, if any. `catalog` is the merged
+/// catalog from `load_merged_catalog`, loaded once by callers that scan many repos.
+pub(crate) fn catalog_skills_path(catalog: &[MergedRepo], owner: &str, repo: &str) -> Option<String> {
+    let url = format!("{}/{}", owner, repo);
+    catalog
+        .iter()
+        .find(|r| r.repo.url.eq_ignore_ascii_case(&url))
+        .and_then(|r| r.repo.skills_path.clone())
+}
+
+pub(crate) fn fetch_catalog_text(url: &str) -> Result<String, String> {
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return Err(format!("Catalog URL must be http(s): {}", url));
+    }
+
+    ureq::get(url)
+        .timeout(FETCH_TIMEOUT)
+        .call()
+        .map_err(|e| format!("Failed to download catalog: {}", e))?
+        .into_string()
+        .map_err(|e| format!("Failed to read catalog response: {}", e))
+}
+
+// Download and validate a catalog, replacing the cached copy at `path` only if it is valid
+fn download_catalog(url: &str, path: &Path) -> Result<(), String> {
+    let catalog = parse_catalog(&fetch_catalog_text(url)?)?;
+    let cached = CachedCatalog {
+        url: url.to_string(),
+        fetched_at: chrono_now(),
+        catalog,
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(&cached).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| format!("Failed to cache catalog: {}", e))
+}
+
+fn download_index(url: &str, data_dir: &Path) -> Result<(), String> {
+    let cached = CachedIndex {
+        url: url.to_string(),
+        fetched_at: chrono_now(),
+        index: parse_index(&fetch_catalog_text(url)?)?,
+    };
+
+    fs::create_dir_all(data_dir).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(&cached).map_err(|e| e.to_string())?;
+    fs::write(get_cached_index_path(data_dir), content).map_err(|e| format!("Failed to cache skill index: {}", e))
+}
+
+// Download every catalog `settings` points to into `data_dir`, returning (source name, error) pairs
+fn download_catalogs(settings: &Settings, data_dir: &Path) -> Vec<(String, String)> {
+    let official = official_name(settings);
+    let mut errors: Vec<(String, String)> = Vec::new();
+
+    let mut downloads: Vec<(String, String, PathBuf)> = Vec::new();
+    if let Some(url) = settings.catalog_url.as_ref().filter(|url| !url.trim().is_empty()) {
+        downloads.push((official.clone(), url.clone(), get_cached_catalog_path(data_dir)));
+    }
+    for source in settings.catalog_sources.iter().filter(|s| s.enabled && s.kind == "remote") {
+        // A misconfigured source is reported on its own and the others still refresh
+        match source_location(source) {
+            Ok(url) => {
+                let path = get_source_cache_path(data_dir, url);
+                downloads.push((source.name.clone(), url.to_string(), path));
+            }
+            Err(e) => errors.push((source.name.clone(), e)),
+        }
+    }
+
+    errors.extend(
+        downloads
+            .into_iter()
+            .filter_map(|(name, url, path)| download_catalog(&url, &path).err().map(|e| (name, e))),
+    );
+    // The index belongs to the official catalog, so its errors are reported there
+    if let Some(url) = settings.index_url.as_ref().filter(|url| !url.trim().is_empty()) {
+        if let Err(e) = download_index(url, data_dir) {
+            errors.push((official, format!("Skill index: {}", e)));
+        }
+    }
+    errors
+}
+
+fn official_name(settings: &Settings) -> String {
+    settings
+        .catalog_sources
+        .iter()
+        .find(|s| s.kind == "bundled")
+        .map(|s| s.name.clone())
+        .unwrap_or_else(|| "official".to_string())
+}
+
+fn source_infos(errors: &[(String, String)]) -> Vec<CatalogInfo> {
+    load_settings()
+        .catalog_sources
+        .iter()
+        .map(|source| {
+            let mut info = match load_source(source) {
+                Ok((_, info)) => info,
+                Err(e) => CatalogInfo {
+                    name: source.name.clone(),
+                    source: source.kind.clone(),
+                    version: String::new(),
+                    last_updated: String::new(),
+                    url: source.location.clone(),
+                    fetched_at: None,
+                    repo_count: 0,
+                    error: Some(e),
+                },
+            };
+            // A failed refresh matters more than the stale copy still in use
+            if let Some((_, error)) = errors.iter().find(|(name, _)| name == &source.name) {
+                info.error = Some(error.clone());
+            }
+            info
+        })
+        .collect()
+}
+
+/// The official catalog in use (downloaded or bundled).
+#[tauri::command]
+pub async fn get_catalog_info() -> Result<CatalogInfo, String> {
+    load_active_catalog().map(|(_, mut info)| {
+        info.name = "official".to_string();
+        info
+    })
+}
+
+/// Every configured catalog source with what it currently provides.
+#[tauri::command]
+pub async fn get_catalog_sources() -> Result<Vec<CatalogInfo>, String> {
+    Ok(source_infos(&[]))
+}
+
+/// Download the official catalog and skill index updates and every enabled remote source.
+/// A failed download or invalid catalog keeps the previous copy and is reported on its source.
+#[tauri::command]
+pub async fn refresh_catalog() -> Result<Vec<CatalogInfo>, String> {
+    let settings = load_settings();
+
+    let errors = tauri::async_runtime::spawn_blocking(move || download_catalogs(&settings, &get_data_path()))
+        .await
+        .map_err(|e| format!("Failed to refresh catalogs: {}", e))?;
+
+    Ok(source_infos(&errors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::paths::test_dir;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    fn catalog_json(version: &str, last_updated: &str) -> String {
+        format!(
+            r#"{{"version": "{}", "lastUpdated": "{}", "repos": [{{"url": "acme/skills"}}]}}"#,
+            version, last_updated
+        )
+    }
+
+    // Answer a single request on 127.0.0.1 with `body`, returning the URL to fetch
+    fn serve_once(body: String) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/catalog.json", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                match stream.read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => request.extend_from_slice(&buffer[..n]),
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        url
+    }
+
+    // A library folder with a bundled catalog and the settings of an official update URL
+    fn setup(label: &str, url: String) -> (PathBuf, PathBuf, Settings) {
+        let root = test_dir(label);
+        let library = root.join("library");
+        fs::create_dir_all(&library).unwrap();
+        fs::write(library.join("catalog.json"), catalog_json("1.0", "2024-01-01")).unwrap();
+        let settings = Settings {
+            catalog_url: Some(url),
+            catalog_sources: Vec::new(),
+            index_url: None,
+            ..Settings::default()
+        };
+        (library, root.join("data"), settings)
+    }
+
+    #[test]
+    fn caches_downloaded_catalog() {
+        let url = serve_once(catalog_json("1.2", "2025-06-01"));
+        let (library, data_dir, settings) = setup("catalog-valid", url.clone());
+
+        assert!(download_catalogs(&settings, &data_dir).is_empty());
+        let (catalog, info) = active_catalog(&library, &data_dir).unwrap();
+        assert_eq!(info.source, "remote");
+        assert_eq!(info.url.as_deref(), Some(url.as_str()));
+        assert_eq!(catalog.last_updated, "2025-06-01");
+    }
+
+    #[test]
+    fn last_updated_compares_as_a_date() {
+        assert_eq!(parse_last_updated("2026-02-25"), Some([2026, 2, 25, 0, 0, 0]));
+        assert_eq!(parse_last_updated("2026-2-25T08:30:00.5Z"), Some([2026, 2, 25, 8, 30, 0]));
+        assert!(parse_last_updated("2026-10-01") > parse_last_updated("2026-9-30"));
+        assert!(parse_last_updated("2026-09-30T12:00:00Z") > parse_last_updated("2026-09-30"));
+        for invalid in ["", "soon", "2026-13-01", "2026-02", "2026-02-25T25:00"] {
+            assert_eq!(parse_last_updated(invalid), None, "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn newer_bundled_catalog_wins_whatever_its_format() {
+        let url = serve_once(catalog_json("1.2", "2024-9-30"));
+        let (library, data_dir, settings) = setup("catalog-dates", url);
+        fs::write(library.join("catalog.json"), catalog_json("1.0", "2024-10-01")).unwrap();
+
+        assert!(download_catalogs(&settings, &data_dir).is_empty());
+        let (catalog, info) = active_catalog(&library, &data_dir).unwrap();
+        assert_eq!(info.source, "bundled");
+        assert_eq!(catalog.last_updated, "2024-10-01");
+    }
+
+    #[test]
+    fn rejects_unsupported_version() {
+        let url = serve_once(catalog_json("2.0", "2025-06-01"));
+        let (library, data_dir, settings) = setup("catalog-version", url);
+
+        let errors = download_catalogs(&settings, &data_dir);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].1.contains("Unsupported catalog version 2.0"), "{}", errors[0].1);
+        assert!(!get_cached_catalog_path(&data_dir).exists());
+        assert_eq!(active_catalog(&library, &data_dir).unwrap().1.source, "bundled");
+    }
+
+    #[test]
+    fn source_without_location_does_not_stop_refresh() {
+        let url = serve_once(catalog_json("1.2", "2025-06-01"));
+        let (library, data_dir, mut settings) = setup("catalog-no-location", url);
+        settings.catalog_sources.push(CatalogSource {
+            name: "team".to_string(),
+            kind: "remote".to_string(),
+            location: None,
+            enabled: true,
+        });
+
+        let errors = download_catalogs(&settings, &data_dir);
+        assert_eq!(errors, vec![("team".to_string(), "Catalog 'team' has no location".to_string())]);
+        assert_eq!(active_catalog(&library, &data_dir).unwrap().1.source, "remote");
+    }
+
+    #[test]
+    fn unreachable_url_falls_back_to_bundled() {
+        // Bind then drop a listener so nothing is serving on the port
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let url = format!("http://127.0.0.1:{}/catalog.json", port);
+        let (library, data_dir, settings) = setup("catalog-unreachable", url);
+
+        let errors = download_catalogs(&settings, &data_dir);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].1.starts_with("Failed to download catalog"), "{}", errors[0].1);
+        let (catalog, info) = active_catalog(&library, &data_dir).unwrap();
+        assert_eq!(info.source, "bundled");
+        assert_eq!(catalog.last_updated, "2024-01-01");
+    }
+}
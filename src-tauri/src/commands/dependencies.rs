@@ -0,0 +1,248 @@
+use super::skills::collect_all_skills;
+use crate::models::{DependencyNode, Skill};
+use std::collections::HashSet;
+
+/// Resolve a dependency reference declared by `from`.
+/// Full ids (`owner/repo/skill`) must match exactly; bare names prefer the same repo.
+pub(crate) fn resolve_dependency<'a>(
+    from: &Skill,
+    reference: &str,
+    skills: &'a [Skill],
+) -> Option<&'a Skill> {
+    if reference.matches('/').count() == 2 {
+        return skills.iter().find(|s| s.id == reference);
+    }
+
+    let matches = |s: &&Skill| s.name == reference || s.path == reference;
+    skills
+        .iter()
+        .filter(matches)
+        .find(|s| s.owner == from.owner && s.repo == from.repo)
+        .or_else(|| skills.iter().find(matches))
+}
+
+/// Dependencies of `skill` that still need installing, deepest first, plus the
+/// references that could not be resolved against the fetched repos.
+pub(crate) fn missing_dependencies(
+    skill: &Skill,
+    skills: &[Skill],
+) -> (Vec<Skill>, Vec<String>) {
+    let mut visited = HashSet::new();
+    let mut missing = Vec::new();
+    let mut unresolved = Vec::new();
+    visited.insert(skill.id.clone());
+    collect_missing(skill, skills, &mut visited, &mut missing, &mut unresolved);
+    (missing, unresolved)
+}
+
+fn collect_missing(
+    skill: &Skill,
+    skills: &[Skill],
+    visited: &mut HashSet<String>,
+    missing: &mut Vec<Skill>,
+    unresolved: &mut Vec<String>,
+) {
+    for reference in &skill.dependencies {
+        let Some(dependency) = resolve_dependency(skill, reference, skills) else {
+            unresolved.push(format!(
+                "Dependency '{}' of '{}' not found in fetched repos",
+                reference, skill.name
+            ));
+            continue;
+        };
+
+        if !visited.insert(dependency.id.clone()) {
+            continue;
+        }
+        collect_missing(dependency, skills, visited, missing, unresolved);
+        if !dependency.is_installed {
+            missing.push(dependency.clone());
+        }
+    }
+}
+
+/// Names of installed skills that depend on the installed skill `skill_name`.
+pub(crate) fn installed_dependents(skill_name: &str, skills: &[Skill]) -> Vec<String> {
+    skills
+        .iter()
+        .filter(|s| s.is_installed && s.name != skill_name && s.path != skill_name)
+        .filter(|s| {
+            s.dependencies.iter().any(|reference| {
+                resolve_dependency(s, reference, skills)
+                    .map(|d| d.name == skill_name || d.path == skill_name)
+                    .unwrap_or(false)
+            })
+        })
+        .map(|s| s.name.clone())
+        .collect()
+}
+
+fn build_tree(
+    reference: &str,
+    skill: Option<&Skill>,
+    skills: &[Skill],
+    ancestors: &mut Vec<String>,
+) -> DependencyNode {
+    let Some(skill) = skill else {
+        return DependencyNode {
+            reference: reference.to_string(),
+            skill_id: None,
+            name: reference.to_string(),
+            is_installed: false,
+            is_resolved: false,
+            is_cycle: false,
+            dependencies: Vec::new(),
+        };
+    };
+
+    let is_cycle = ancestors.contains(&skill.id);
+    let mut dependencies = Vec::new();
+    if !is_cycle {
+        ancestors.push(skill.id.clone());
+        for dep in &skill.dependencies {
+            let resolved = resolve_dependency(skill, dep, skills);
+            dependencies.push(build_tree(dep, resolved, skills, ancestors));
+        }
+        ancestors.pop();
+    }
+
+    DependencyNode {
+        reference: reference.to_string(),
+        skill_id: Some(skill.id.clone()),
+        name: skill.name.clone(),
+        is_installed: skill.is_installed,
+        is_resolved: true,
+        is_cycle,
+        dependencies,
+    }
+}
+
+#[tauri::command]
+pub async fn get_skill_dependencies(
+    skill_id: String,
+) -> Result<DependencyNode, String> {
+    let skills = collect_all_skills();
+    let skill = skills
+        .iter()
+        .find(|s| s.id == skill_id)
+        .ok_or_else(|| format!("Skill {} not found", skill_id))?;
+
+    Ok(build_tree(&skill_id, Some(skill), &skills, &mut Vec::new()))
+}
+
+#[tauri::command]
+pub async fn get_skill_dependents(
+    skill_name: String,
+) -> Result<Vec<String>, String> {
+    Ok(installed_dependents(&skill_name, &collect_all_skills()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill(id: &str, dependencies: &[&str], is_installed: bool) -> Skill {
+        let parts: Vec<&str> = id.split('/').collect();
+        Skill {
+            id: id.to_string(),
+            name: parts[2].to_string(),
+            description: String::new(),
+            owner: parts[0].to_string(),
+            repo: parts[1].to_string(),
+            skills_path: "skills".to_string(),
+            path: parts[2].to_string(),
+            content: None,
+            is_installed,
+            is_fetched: true,
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            is_disabled: false,
+            installs: None,
+            recent_installs: None,
+        }
+    }
+
+    fn ids(skills: &[Skill]) -> Vec<&str> {
+        skills.iter().map(|s| s.id.as_str()).collect()
+    }
+
+    #[test]
+    fn bare_names_prefer_the_same_repo() {
+        let skills = vec![
+            skill("a/one/pdf", &[], false),
+            skill("b/two/pdf", &[], false),
+            skill("b/two/docs", &["pdf"], false),
+        ];
+        let resolved = resolve_dependency(&skills[2], "pdf", &skills).unwrap();
+        assert_eq!(resolved.id, "b/two/pdf");
+
+        let resolved = resolve_dependency(&skills[2], "a/one/pdf", &skills).unwrap();
+        assert_eq!(resolved.id, "a/one/pdf");
+        assert!(resolve_dependency(&skills[2], "c/three/pdf", &skills).is_none());
+    }
+
+    #[test]
+    fn missing_dependencies_are_deepest_first_and_skip_installed() {
+        let skills = vec![
+            skill("o/r/app", &["lib", "tool"], false),
+            skill("o/r/lib", &["base"], false),
+            skill("o/r/base", &[], false),
+            skill("o/r/tool", &[], true),
+        ];
+        let (missing, unresolved) = missing_dependencies(&skills[0], &skills);
+        assert_eq!(ids(&missing), ["o/r/base", "o/r/lib"]);
+        assert!(unresolved.is_empty());
+    }
+
+    #[test]
+    fn unresolved_dependencies_are_reported_without_failing() {
+        let skills = vec![
+            skill("o/r/app", &["lib", "ghost"], false),
+            skill("o/r/lib", &["x/y/gone"], false),
+        ];
+        let (missing, unresolved) = missing_dependencies(&skills[0], &skills);
+        assert_eq!(ids(&missing), ["o/r/lib"]);
+        assert_eq!(unresolved.len(), 2);
+        assert!(unresolved[0].contains("'x/y/gone' of 'lib'"));
+        assert!(unresolved[1].contains("'ghost' of 'app'"));
+    }
+
+    #[test]
+    fn cycles_terminate() {
+        let skills = vec![
+            skill("o/r/a", &["b"], false),
+            skill("o/r/b", &["a"], false),
+        ];
+        let (missing, unresolved) = missing_dependencies(&skills[0], &skills);
+        assert_eq!(ids(&missing), ["o/r/b"]);
+        assert!(unresolved.is_empty());
+
+        let tree = build_tree("o/r/a", Some(&skills[0]), &skills, &mut Vec::new());
+        let b = &tree.dependencies[0];
+        assert!(!tree.is_cycle && !b.is_cycle);
+        assert!(b.dependencies[0].is_cycle);
+        assert!(b.dependencies[0].dependencies.is_empty());
+    }
+
+    #[test]
+    fn tree_marks_unresolved_references() {
+        let skills = vec![skill("o/r/a", &["ghost"], false)];
+        let tree = build_tree("o/r/a", Some(&skills[0]), &skills, &mut Vec::new());
+        assert!(tree.is_resolved);
+        assert!(!tree.dependencies[0].is_resolved);
+        assert_eq!(tree.dependencies[0].name, "ghost");
+    }
+
+    #[test]
+    fn dependents_are_installed_skills_only() {
+        let skills = vec![
+            skill("o/r/base", &[], true),
+            skill("o/r/app", &["base"], true),
+            skill("o/r/draft", &["base"], false),
+            skill("x/y/other", &["o/r/base"], true),
+            skill("x/y/base", &[], true),
+            skill("x/y/local", &["base"], true),
+        ];
+        assert_eq!(installed_dependents("base", &skills), ["app", "other", "local"]);
+        assert!(installed_dependents("app", &skills).is_empty());
+    }
+}
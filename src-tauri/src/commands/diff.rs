@@ -0,0 +1,239 @@
+use super::agents::{agent_target, user_targets};
+use super::bundles::resolve_member;
+use super::catalog::load_merged_catalog;
+use super::hashing::file_hashes;
+use super::paths::SkillName;
+use super::skills::{cached_skill_dir, collect_all_skills, list_installed_skill_names};
+use crate::models::{BundleSkill, DiffHunk, DiffLine, DiffSide, FileDiff, Skill, SkillDiff};
+use similar::{ChangeTag, TextDiff};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_CONTEXT: usize = 3;
+
+// Folder holding one side of the diff, with a label for the UI
+fn resolve_side(side: &DiffSide, skills: &[Skill]) -> Result<(PathBuf, String), String> {
+    match side {
+        DiffSide::Installed {
+            skill_name,
+            agent,
+            scope,
+        } => {
+            let name = SkillName::parse(skill_name)?;
+            let targets = match agent {
+                Some(agent) => vec![agent_target(agent, scope.as_deref())?],
+                None => user_targets(),
+            };
+            let found = targets
+                .iter()
+                .flat_map(|t| [(t.agent, t.root.join(&name)), (t.agent, t.disabled_root().join(&name))])
+                .find(|(_, path)| path.is_dir());
+            found
+                .map(|(agent, path)| (path, format!("{} (installed, {})", name, agent)))
+                .ok_or_else(|| format!("Skill '{}' is not installed", name))
+        }
+        DiffSide::Cached { skill_id } => {
+            let skill = skills
+                .iter()
+                .find(|s| &s.id == skill_id)
+                .ok_or_else(|| format!("Skill {} not found", skill_id))?;
+            Ok((cached_skill_dir(skill)?, skill_id.clone()))
+        }
+        DiffSide::Commit { skill_id, git_ref } => {
+            let member = BundleSkill {
+                id: skill_id.clone(),
+                git_ref: Some(git_ref.clone()),
+            };
+            // Fetching re-clones branch checkouts, so a branch is compared at its current head
+            let (_, source) = resolve_member(&member, skills, &list_installed_skill_names(), &load_merged_catalog().0, true)?;
+            Ok((source, format!("{}@{}", skill_id, git_ref)))
+        }
+    }
+}
+
+fn hunks(old: &str, new: &str, context: usize) -> Vec<DiffHunk> {
+    let diff = TextDiff::from_lines(old, new);
+
+    diff.grouped_ops(context)
+        .into_iter()
+        .filter_map(|group| {
+            let (first, last) = (group.first()?, group.last()?);
+            let old_range = first.old_range().start..last.old_range().end;
+            let new_range = first.new_range().start..last.new_range().end;
+
+            let lines = group
+                .iter()
+                .flat_map(|op| diff.iter_changes(op))
+                .map(|change| DiffLine {
+                    kind: match change.tag() {
+                        ChangeTag::Equal => "context",
+                        ChangeTag::Insert => "added",
+                        ChangeTag::Delete => "removed",
+                    }
+                    .to_string(),
+                    content: change.value().trim_end_matches(['\n', '\r']).to_string(),
+                    old_line: change.old_index().map(|i| i + 1),
+                    new_line: change.new_index().map(|i| i + 1),
+                })
+                .collect();
+
+            Some(DiffHunk {
+                // Unified diff convention: an empty range starts at the line before it
+                old_start: if old_range.is_empty() { old_range.start } else { old_range.start + 1 },
+                old_lines: old_range.len(),
+                new_start: if new_range.is_empty() { new_range.start } else { new_range.start + 1 },
+                new_lines: new_range.len(),
+                lines,
+            })
+        })
+        .collect()
+}
+
+// None for binary (non UTF-8) or unreadable files
+fn read_text(path: &Path) -> Option<String> {
+    fs::read(path).ok().and_then(|bytes| String::from_utf8(bytes).ok())
+}
+
+pub(crate) fn diff_dirs(old_dir: &Path, new_dir: &Path, context: usize) -> Result<Vec<FileDiff>, String> {
+    let old_files = file_hashes(old_dir)?;
+    let new_files = file_hashes(new_dir)?;
+    let paths: BTreeSet<&String> = old_files.keys().chain(new_files.keys()).collect();
+
+    let mut files = Vec::new();
+    for path in paths {
+        let (old_hash, new_hash) = (old_files.get(path), new_files.get(path));
+        if old_hash == new_hash {
+            continue;
+        }
+
+        let status = match (old_hash, new_hash) {
+            (None, _) => "added",
+            (_, None) => "removed",
+            _ => "modified",
+        };
+        // Outer None: file absent on that side, inner None: binary
+        let old = old_hash.map(|_| read_text(&old_dir.join(path)));
+        let new = new_hash.map(|_| read_text(&new_dir.join(path)));
+        let is_binary = matches!(old, Some(None)) || matches!(new, Some(None));
+
+        let hunks = if is_binary {
+            Vec::new()
+        } else {
+            hunks(&old.flatten().unwrap_or_default(), &new.flatten().unwrap_or_default(), context)
+        };
+
+        files.push(FileDiff {
+            path: path.clone(),
+            status: status.to_string(),
+            is_binary,
+            hunks,
+        });
+    }
+
+    Ok(files)
+}
+
+/// File-by-file unified diff between two versions of a skill: installed vs. cached,
+/// two commits of a repo, or same-named skills from different repos.
+#[tauri::command]
+pub async fn diff_skill_versions(
+    left: DiffSide,
+    right: DiffSide,
+    context: Option<usize>,
+) -> Result<SkillDiff, String> {
+    let skills = collect_all_skills();
+    let (left_dir, left_label) = resolve_side(&left, &skills)?;
+    let (right_dir, right_label) = resolve_side(&right, &skills)?;
+
+    Ok(SkillDiff {
+        files: diff_dirs(&left_dir, &right_dir, context.unwrap_or(DEFAULT_CONTEXT))?,
+        left_label,
+        right_label,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::paths::test_dir;
+
+    fn summary(hunk: &DiffHunk) -> (usize, usize, usize, usize) {
+        (hunk.old_start, hunk.old_lines, hunk.new_start, hunk.new_lines)
+    }
+
+    fn lines(hunk: &DiffHunk) -> Vec<(&str, Option<usize>, Option<usize>)> {
+        hunk.lines
+            .iter()
+            .map(|l| (l.kind.as_str(), l.old_line, l.new_line))
+            .collect()
+    }
+
+    #[test]
+    fn hunks_use_one_based_line_numbers() {
+        let hunks = hunks("a\nb\nc\n", "a\nB\nc\n", 1);
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(summary(&hunks[0]), (1, 3, 1, 3));
+        assert_eq!(
+            lines(&hunks[0]),
+            [
+                ("context", Some(1), Some(1)),
+                ("removed", Some(2), None),
+                ("added", None, Some(2)),
+                ("context", Some(3), Some(3)),
+            ]
+        );
+        assert_eq!(hunks[0].lines[1].content, "b");
+    }
+
+    #[test]
+    fn empty_ranges_start_at_the_line_before() {
+        // Inserting after line 2 of a file with no context: nothing on the old side
+        let hunks = hunks("a\nb\n", "a\nb\nc\n", 0);
+        assert_eq!(summary(&hunks[0]), (2, 0, 3, 1));
+
+        // A new file starts at line 0 on the old side
+        let hunks = super::hunks("", "x\n", 3);
+        assert_eq!(summary(&hunks[0]), (0, 0, 1, 1));
+
+        let hunks = super::hunks("x\n", "", 3);
+        assert_eq!(summary(&hunks[0]), (1, 1, 0, 0));
+    }
+
+    #[test]
+    fn distant_changes_get_separate_hunks() {
+        let line = |i: usize| match i {
+            3 => "three\n".to_string(),
+            18 => "eighteen\n".to_string(),
+            i => format!("{}\n", i),
+        };
+        let old: String = (1..=20).map(|i| format!("{}\n", i)).collect();
+        let new: String = (1..=20).map(line).collect();
+        let hunks = hunks(&old, &new, 2);
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(summary(&hunks[0]), (1, 5, 1, 5));
+        assert_eq!(summary(&hunks[1]), (16, 5, 16, 5));
+    }
+
+    #[test]
+    fn diff_dirs_reports_status_and_binary_files() {
+        let dir = test_dir("diff-dirs");
+        let (old, new) = (dir.join("old"), dir.join("new"));
+        for side in [&old, &new] {
+            fs::create_dir_all(side).unwrap();
+            fs::write(side.join("same.md"), "same\n").unwrap();
+        }
+        fs::write(old.join("SKILL.md"), "one\n").unwrap();
+        fs::write(new.join("SKILL.md"), "two\n").unwrap();
+        fs::write(old.join("gone.md"), "gone\n").unwrap();
+        fs::write(new.join("image.png"), [0xff, 0xfe, 0x00]).unwrap();
+
+        let files = diff_dirs(&old, &new, 3).unwrap();
+        let statuses: Vec<_> = files.iter().map(|f| (f.path.as_str(), f.status.as_str(), f.is_binary)).collect();
+        assert_eq!(
+            statuses,
+            [("SKILL.md", "modified", false), ("gone.md", "removed", false), ("image.png", "added", true)]
+        );
+        assert!(files[2].hunks.is_empty());
+    }
+}
@@ -0,0 +1,65 @@
+use super::agents::selected_targets;
+use super::install::target_status;
+use super::paths::{resolve_install_path, SkillName};
+use crate::models::TargetStatus;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Move `skill_name` from `from` to `to`, refusing to overwrite an existing folder
+fn move_skill(from: &Path, to: &Path, skill_name: &SkillName) -> Result<PathBuf, String> {
+    let source = from.join(skill_name);
+    let dest = resolve_install_path(to, skill_name)?;
+
+    if dest.symlink_metadata().is_ok() {
+        return Err(format!("Skill '{}' already exists in {:?}", skill_name, to));
+    }
+
+    fs::rename(&source, &dest).map_err(|e| format!("Failed to move skill: {}", e))?;
+    Ok(dest)
+}
+
+fn move_in_targets(
+    skill_name: &str,
+    scope: Option<String>,
+    disable: bool,
+) -> Result<Vec<TargetStatus>, String> {
+    let skill_name = SkillName::parse(skill_name)?;
+    let targets = selected_targets(scope.as_deref())?;
+
+    let statuses: Vec<TargetStatus> = targets
+        .iter()
+        .filter_map(|target| {
+            let (from, to) = if disable {
+                (target.root.clone(), target.disabled_root())
+            } else {
+                (target.disabled_root(), target.root.clone())
+            };
+            if from.join(&skill_name).symlink_metadata().is_err() {
+                return None;
+            }
+            Some(target_status(target, &skill_name, move_skill(&from, &to, &skill_name)))
+        })
+        .collect();
+
+    if statuses.is_empty() {
+        let state = if disable { "installed" } else { "disabled" };
+        return Err(format!("Skill '{}' is not {}", skill_name, state));
+    }
+    Ok(statuses)
+}
+
+#[tauri::command]
+pub async fn disable_skill(
+    skill_name: String,
+    scope: Option<String>,
+) -> Result<Vec<TargetStatus>, String> {
+    move_in_targets(&skill_name, scope, true)
+}
+
+#[tauri::command]
+pub async fn enable_skill(
+    skill_name: String,
+    scope: Option<String>,
+) -> Result<Vec<TargetStatus>, String> {
+    move_in_targets(&skill_name, scope, false)
+}
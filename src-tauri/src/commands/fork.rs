@@ -0,0 +1,392 @@
+use super::authoring::validate_new_name;
+use super::catalog::load_merged_catalog;
+use super::diff::diff_dirs;
+use super::hashing::file_hashes;
+use super::git::github_url;
+use super::install::{copy_skill_dir, load_install_records};
+use super::lockfile::commit_checkout;
+use super::paths::{get_data_path, get_local_skills_path, parse_relative_path, unique_staging_path, SkillName};
+use super::skills::{
+    cached_skill_dir, chrono_now, collect_all_skills, fetch_repo_to_cache, find_skill_file,
+    installed_skill_dir, load_fetched_repos, set_frontmatter_name,
+};
+use crate::models::{ForkInfo, ForkStatus, MergeResult, Skill};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DIFF_CONTEXT: usize = 3;
+
+// Registry of forks, next to the upstream snapshots they are merged against
+fn get_forks_path() -> PathBuf {
+    get_data_path().join("forks.json")
+}
+
+// Upstream content as of the fork (or last merge), renamed like the fork
+fn get_base_path(name: &SkillName) -> PathBuf {
+    get_data_path().join("fork-bases").join(name)
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Default)]
+struct Forks {
+    forks: Vec<ForkInfo>,
+}
+
+fn load_forks() -> Forks {
+    fs::read_to_string(get_forks_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_forks(forks: &Forks) -> Result<(), String> {
+    fs::create_dir_all(get_data_path()).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(forks).map_err(|e| e.to_string())?;
+    fs::write(get_forks_path(), content).map_err(|e| e.to_string())
+}
+
+fn find_fork(name: &str) -> Result<ForkInfo, String> {
+    load_forks()
+        .forks
+        .into_iter()
+        .find(|f| f.name == name)
+        .ok_or_else(|| format!("Fork '{}' not found", name))
+}
+
+fn repo_commit(skill: &Skill) -> Option<String> {
+    load_fetched_repos()
+        .commits
+        .get(&format!("{}/{}", skill.owner, skill.repo))
+        .cloned()
+}
+
+fn rename_skill(dir: &Path, name: &SkillName) -> Result<(), String> {
+    let skill_file = find_skill_file(dir).ok_or("Skill folder has no SKILL.md")?;
+    let content = fs::read_to_string(&skill_file).map_err(|e| format!("Failed to read SKILL.md: {}", e))?;
+    let renamed = set_frontmatter_name(&content, name.as_str()).ok_or("SKILL.md frontmatter has no name")?;
+    fs::write(&skill_file, renamed).map_err(|e| format!("Failed to write SKILL.md: {}", e))
+}
+
+// Fresh copy of `source` at `dest`, renamed to the fork's name
+fn copy_renamed(source: &Path, dest: &Path, name: &SkillName) -> Result<(), String> {
+    fs::remove_dir_all(dest).ok();
+    copy_skill_dir(source, dest)?;
+    rename_skill(dest, name)
+}
+
+// Latest upstream version staged under the fork's name, with its commit
+fn stage_upstream(
+    fork: &ForkInfo,
+    name: &SkillName,
+    refresh: bool,
+) -> Result<(PathBuf, Option<String>), String> {
+    if refresh {
+        let mut parts = fork.upstream_id.splitn(3, '/');
+        if let (Some(owner), Some(repo)) = (parts.next(), parts.next()) {
+            fetch_repo_to_cache(owner, repo, &load_merged_catalog().0)?;
+        }
+    }
+
+    let skill = collect_all_skills()
+        .into_iter()
+        .find(|s| s.id == fork.upstream_id)
+        .ok_or_else(|| format!("Upstream skill {} is no longer available", fork.upstream_id))?;
+
+    let staging = get_data_path().join("fork-staging").join(name);
+    copy_renamed(&cached_skill_dir(&skill)?, &staging, name)?;
+    Ok((staging, repo_commit(&skill)))
+}
+
+#[tauri::command]
+pub async fn list_forks() -> Result<Vec<ForkInfo>, String> {
+    Ok(load_forks().forks)
+}
+
+/// Copy a cached (or installed) skill into the local workspace as `name`,
+/// recording where it came from so upstream changes can be merged later.
+#[tauri::command]
+pub async fn fork_skill(
+    skill_id: String,
+    name: String,
+    from_installed: Option<bool>,
+) -> Result<ForkInfo, String> {
+    validate_new_name(&name)?;
+    let fork_name = SkillName::parse(&name)?;
+    let dest = get_local_skills_path().join(&fork_name);
+    let mut forks = load_forks();
+
+    if dest.exists() || forks.forks.iter().any(|f| f.name == name) {
+        return Err(format!("A local skill named '{}' already exists", name));
+    }
+
+    let skill = collect_all_skills()
+        .into_iter()
+        .find(|s| s.id == skill_id)
+        .ok_or_else(|| format!("Skill {} not found", skill_id))?;
+    let cached = cached_skill_dir(&skill)?;
+    let (source, upstream, upstream_commit) = if from_installed.unwrap_or(false) {
+        // The base is the upstream version the copy was installed from, so its local
+        // edits show up as the fork's own changes
+        let installed =
+            installed_skill_dir(&skill).ok_or_else(|| format!("Skill '{}' is not installed", skill.name))?;
+        let commit = load_install_records()
+            .get(installed.to_string_lossy().as_ref())
+            .and_then(|r| r.commit.clone())
+            .ok_or_else(|| {
+                format!(
+                    "No upstream commit recorded for installed '{}', reinstall it or fork the cached version",
+                    skill.name
+                )
+            })?;
+        let upstream = if repo_commit(&skill).as_ref() == Some(&commit) {
+            cached
+        } else {
+            commit_checkout(&github_url(&skill.owner, &skill.repo), &skill.owner, &skill.repo, &commit)?
+                .join(parse_relative_path(&skill.skills_path)?)
+                .join(parse_relative_path(&skill.path)?)
+        };
+        (installed, upstream, Some(commit))
+    } else {
+        (cached.clone(), cached, repo_commit(&skill))
+    };
+
+    let base = get_base_path(&fork_name);
+    copy_renamed(&upstream, &base, &fork_name)
+        .and_then(|_| copy_renamed(&source, &dest, &fork_name))
+        .inspect_err(|_| {
+            fs::remove_dir_all(&base).ok();
+            fs::remove_dir_all(&dest).ok();
+        })?;
+
+    let fork = ForkInfo {
+        name,
+        path: dest.to_string_lossy().to_string(),
+        upstream_id: skill.id.clone(),
+        upstream_name: skill.name.clone(),
+        upstream_commit,
+        forked_at: chrono_now(),
+        merged_at: None,
+    };
+    forks.forks.push(fork.clone());
+    save_forks(&forks)?;
+
+    Ok(fork)
+}
+
+/// How the fork and upstream have each changed since the fork (or last merge).
+#[tauri::command]
+pub async fn get_fork_status(
+    name: String,
+    refresh: Option<bool>,
+) -> Result<ForkStatus, String> {
+    let fork = find_fork(&name)?;
+    let fork_name = SkillName::parse(&fork.name)?;
+    let base = get_base_path(&fork_name);
+
+    let (staging, _) = stage_upstream(&fork, &fork_name, refresh.unwrap_or(false))?;
+    let status = diff_dirs(&base, Path::new(&fork.path), DIFF_CONTEXT).and_then(|local_changes| {
+        Ok(ForkStatus {
+            local_changes,
+            upstream_changes: diff_dirs(&base, &staging, DIFF_CONTEXT)?,
+            fork,
+        })
+    });
+    fs::remove_dir_all(&staging).ok();
+    status
+}
+
+/// Three-way merge of upstream changes into the fork. Files changed on both sides
+/// are merged line by line; overlapping edits are left with conflict markers.
+#[tauri::command]
+pub async fn merge_upstream(
+    name: String,
+    refresh: Option<bool>,
+) -> Result<MergeResult, String> {
+    let fork = find_fork(&name)?;
+    let fork_name = SkillName::parse(&fork.name)?;
+    let base = get_base_path(&fork_name);
+    let ours = PathBuf::from(&fork.path);
+
+    let (theirs, upstream_commit) = stage_upstream(&fork, &fork_name, refresh.unwrap_or(true))?;
+    let result = merge_in_place(&base, &ours, &theirs, &unique_staging_path("fork-merge"))
+        // The merged upstream version becomes the base for the next merge
+        .and_then(|result| {
+            fs::remove_dir_all(&base).ok();
+            copy_skill_dir(&theirs, &base).map(|_| result)
+        });
+    fs::remove_dir_all(&theirs).ok();
+    let mut result = result?;
+
+    let mut forks = load_forks();
+    if let Some(entry) = forks.forks.iter_mut().find(|f| f.name == fork.name) {
+        entry.upstream_commit = upstream_commit.clone();
+        entry.merged_at = Some(chrono_now());
+    }
+    save_forks(&forks)?;
+
+    result.upstream_commit = upstream_commit;
+    Ok(result)
+}
+
+/// Merge into a copy of `ours` under `staging`, then swap the copy in, so a merge that
+/// fails partway leaves the fork as it was.
+fn merge_in_place(base: &Path, ours: &Path, theirs: &Path, staging: &Path) -> Result<MergeResult, String> {
+    let merged = staging.join("merged");
+    let previous = staging.join("previous");
+    let result = copy_skill_dir(ours, &merged)
+        .and_then(|_| merge_dirs(base, &merged, theirs))
+        .and_then(|result| {
+            fs::rename(ours, &previous).map_err(|e| format!("Failed to move the fork aside: {}", e))?;
+            if let Err(e) = fs::rename(&merged, ours) {
+                fs::rename(&previous, ours).ok();
+                return Err(format!("Failed to move the merged fork into place: {}", e));
+            }
+            Ok(result)
+        });
+    fs::remove_dir_all(staging).ok();
+    result
+}
+
+fn merge_dirs(base: &Path, ours: &Path, theirs: &Path) -> Result<MergeResult, String> {
+    let base_files = file_hashes(base)?;
+    let our_files = file_hashes(ours)?;
+    let their_files = file_hashes(theirs)?;
+
+    let mut paths: Vec<&String> = base_files.keys().chain(our_files.keys()).chain(their_files.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    let mut result = MergeResult::default();
+    for path in paths {
+        let (b, o, t) = (base_files.get(path), our_files.get(path), their_files.get(path));
+        let relative = parse_relative_path(path)?;
+        let dest = ours.join(&relative);
+
+        if o == t || b == t {
+            continue; // Nothing new upstream, or both made the same change
+        }
+
+        if b == o {
+            // Only upstream changed: take theirs
+            match t {
+                Some(_) => {
+                    if let Some(parent) = dest.parent() {
+                        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                    }
+                    fs::copy(theirs.join(&relative), &dest).map_err(|e| format!("Failed to update {}: {}", path, e))?;
+                    result.updated.push(path.clone());
+                }
+                None => {
+                    fs::remove_file(&dest).map_err(|e| format!("Failed to remove {}: {}", path, e))?;
+                    result.removed.push(path.clone());
+                }
+            }
+            continue;
+        }
+
+        // Both sides changed; a deleted or binary file keeps our version
+        let read = |dir: &Path| fs::read_to_string(dir.join(&relative)).ok();
+        let base_text = if b.is_some() { read(base) } else { Some(String::new()) };
+        match (base_text, o.and(read(ours)), t.and(read(theirs))) {
+            (Some(base_text), Some(our_text), Some(their_text)) => {
+                let merged = diffy::merge(&base_text, &our_text, &their_text);
+                let (content, conflicted) = match merged {
+                    Ok(content) => (content, false),
+                    Err(content) => (content, true),
+                };
+                fs::write(&dest, content).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+                if conflicted {
+                    result.conflicts.push(path.clone());
+                } else {
+                    result.updated.push(path.clone());
+                }
+            }
+            _ => result.conflicts.push(path.clone()),
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::paths::test_dir;
+
+    // Base, fork and upstream folders, each starting with `files`
+    fn setup(label: &str, files: &[(&str, &str)]) -> (PathBuf, PathBuf, PathBuf, PathBuf) {
+        let dir = test_dir(label);
+        let (base, ours, theirs) = (dir.join("base"), dir.join("ours"), dir.join("theirs"));
+        for side in [&base, &ours, &theirs] {
+            fs::create_dir_all(side).unwrap();
+            for (name, content) in files {
+                fs::write(side.join(name), content).unwrap();
+            }
+        }
+        (base, ours, theirs, dir.join("staging"))
+    }
+
+    fn read(dir: &Path, name: &str) -> String {
+        fs::read_to_string(dir.join(name)).unwrap()
+    }
+
+    #[test]
+    fn upstream_additions_and_deletions_are_applied() {
+        let (base, ours, theirs, staging) = setup("merge-add-delete", &[("SKILL.md", "a\n"), ("old.md", "old\n")]);
+        fs::create_dir_all(theirs.join("references")).unwrap();
+        fs::write(theirs.join("references/new.md"), "new\n").unwrap();
+        fs::remove_file(theirs.join("old.md")).unwrap();
+        fs::write(ours.join("mine.md"), "mine\n").unwrap();
+
+        let result = merge_in_place(&base, &ours, &theirs, &staging).unwrap();
+        assert_eq!(result.updated, ["references/new.md"]);
+        assert_eq!(result.removed, ["old.md"]);
+        assert!(result.conflicts.is_empty());
+        assert_eq!(read(&ours, "references/new.md"), "new\n");
+        assert_eq!(read(&ours, "mine.md"), "mine\n");
+        assert!(!ours.join("old.md").exists());
+        assert!(!staging.exists());
+    }
+
+    #[test]
+    fn edits_on_both_sides_merge_or_conflict() {
+        let text = "one\ntwo\nthree\nfour\nfive\n";
+        let (base, ours, theirs, staging) = setup("merge-conflict", &[("SKILL.md", text), ("notes.md", "x\n")]);
+        fs::write(ours.join("SKILL.md"), text.replace("one", "ONE")).unwrap();
+        fs::write(theirs.join("SKILL.md"), text.replace("five", "FIVE")).unwrap();
+        fs::write(ours.join("notes.md"), "ours\n").unwrap();
+        fs::write(theirs.join("notes.md"), "theirs\n").unwrap();
+
+        let result = merge_in_place(&base, &ours, &theirs, &staging).unwrap();
+        assert_eq!(result.updated, ["SKILL.md"]);
+        assert_eq!(result.conflicts, ["notes.md"]);
+        assert_eq!(read(&ours, "SKILL.md"), "ONE\ntwo\nthree\nfour\nFIVE\n");
+        let notes = read(&ours, "notes.md");
+        assert!(notes.contains("<<<<<<<") && notes.contains("ours") && notes.contains("theirs"));
+    }
+
+    #[test]
+    fn deleted_on_one_side_and_edited_on_the_other_keeps_ours() {
+        let (base, ours, theirs, staging) = setup("merge-delete-edit", &[("SKILL.md", "a\n"), ("notes.md", "x\n")]);
+        fs::remove_file(ours.join("notes.md")).unwrap();
+        fs::write(theirs.join("notes.md"), "edited\n").unwrap();
+
+        let result = merge_in_place(&base, &ours, &theirs, &staging).unwrap();
+        assert_eq!(result.conflicts, ["notes.md"]);
+        assert!(!ours.join("notes.md").exists());
+    }
+
+    #[test]
+    fn failed_merge_leaves_the_fork_untouched() {
+        let (base, ours, theirs, staging) = setup("merge-failure", &[("SKILL.md", "a\n")]);
+        fs::write(theirs.join("SKILL.md"), "b\n").unwrap();
+        // Upstream adds a file where the fork has a folder, which cannot be written
+        fs::write(theirs.join("x"), "file\n").unwrap();
+        fs::create_dir_all(ours.join("x")).unwrap();
+        fs::write(ours.join("x/inner.md"), "inner\n").unwrap();
+
+        assert!(merge_in_place(&base, &ours, &theirs, &staging).is_err());
+        assert_eq!(read(&ours, "SKILL.md"), "a\n");
+        assert_eq!(read(&ours, "x/inner.md"), "inner\n");
+        assert!(!staging.exists());
+    }
+}
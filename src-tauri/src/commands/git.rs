@@ -0,0 +1,158 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+pub(crate) fn github_url(owner: &str, repo: &str) -> String {
+    format!("https://github.com/{}/{}.git", owner, repo)
+}
+
+/// Reject refs that git would misread as options or that could escape a cache folder.
+pub(crate) fn validate_git_ref(git_ref: &str) -> Result<(), String> {
+    let valid = !git_ref.is_empty()
+        && !git_ref.starts_with('-')
+        && !git_ref.contains("..")
+        && git_ref
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '/'));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid git ref: '{}'", git_ref))
+    }
+}
+
+/// Whether `git_ref` always names the same commit: a full commit SHA, or a tag on `url`.
+/// Branches move, so checkouts of them have to be fetched again.
+pub(crate) fn is_immutable_ref(url: &str, git_ref: &str) -> Result<bool, String> {
+    if git_ref.len() == 40 && git_ref.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Ok(true);
+    }
+    validate_git_ref(git_ref)?;
+    let tag = format!("refs/tags/{}", git_ref);
+    Ok(!run_git(&["ls-remote", "--tags", url, &tag], None)?.is_empty())
+}
+
+pub(crate) fn run_git(args: &[&str], cwd: Option<&Path>) -> Result<String, String> {
+    let mut command = Command::new("git");
+    command.args(args);
+    if let Some(cwd) = cwd {
+        command.current_dir(cwd);
+    }
+
+    let output = command
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// The commit a clone checked out, read before its `.git` folder is removed.
+pub(crate) struct ClonedCommit {
+    pub sha: String,
+    pub committed_at: String, // Unix seconds
+    pub history_len: usize,   // commits in the (shallow) history that was cloned
+}
+
+/// Shallow clone `url` into `dest` at `git_ref` (branch, tag or commit SHA, default branch if None),
+/// strip the `.git` folder and return the checked out commit.
+pub(crate) fn shallow_clone(url: &str, dest: &Path, git_ref: Option<&str>) -> Result<ClonedCommit, String> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create dir: {}", e))?;
+    }
+    let dest_str = dest.to_str().ok_or("Invalid destination path")?;
+
+    match git_ref {
+        None => {
+            run_git(&["clone", "--depth", "1", url, dest_str], None)?;
+        }
+        Some(git_ref) => {
+            validate_git_ref(git_ref)?;
+            // Fetching by ref works for branches, tags and (on GitHub) full commit SHAs
+            fs::create_dir_all(dest).map_err(|e| format!("Failed to create dir: {}", e))?;
+            run_git(&["init", "--quiet"], Some(dest))?;
+            run_git(&["fetch", "--depth", "1", url, git_ref], Some(dest))?;
+            run_git(&["checkout", "--quiet", "FETCH_HEAD"], Some(dest))?;
+        }
+    }
+
+    let sha = run_git(&["rev-parse", "HEAD"], Some(dest))?;
+    let committed_at = run_git(&["log", "-1", "--format=%ct"], Some(dest))?;
+    let history_len = run_git(&["rev-list", "--count", "HEAD"], Some(dest))?
+        .parse()
+        .unwrap_or(1);
+
+    // Remove .git folder to save space
+    let git_dir = dest.join(".git");
+    if git_dir.exists() {
+        fs::remove_dir_all(&git_dir).ok(); // Ignore errors
+    }
+
+    Ok(ClonedCommit {
+        sha,
+        committed_at,
+        history_len,
+    })
+}
+
+/// Clone only the `SKILL.md` files of `url` into `dest`, enough to count skills without
+/// downloading their content. Returns the latest commit SHA and its Unix timestamp.
+pub(crate) fn sparse_skill_clone(url: &str, dest: &Path) -> Result<(String, u64), String> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create dir: {}", e))?;
+    }
+    let dest_str = dest.to_str().ok_or("Invalid destination path")?;
+
+    run_git(
+        &["clone", "--quiet", "--depth", "1", "--filter=blob:none", "--sparse", url, dest_str],
+        None,
+    )?;
+    run_git(&["sparse-checkout", "set", "--no-cone", "SKILL.md", "skill.md"], Some(dest))?;
+
+    let log = run_git(&["log", "-1", "--format=%H %ct"], Some(dest))?;
+    let (sha, timestamp) = log.split_once(' ').ok_or("Unexpected git log output")?;
+    let timestamp = timestamp
+        .parse()
+        .map_err(|_| format!("Invalid commit timestamp: {}", timestamp))?;
+    Ok((sha.to_string(), timestamp))
+}
+
+/// Git config arguments giving commits a fixed identity, which CI machines often lack.
+#[cfg(test)]
+pub(crate) const TEST_IDENTITY: [&str; 4] =
+    ["-c", "user.name=Skill Studio Tests", "-c", "user.email=tests@skill-studio.invalid"];
+
+/// Run git in `cwd` with the test identity.
+#[cfg(test)]
+pub(crate) fn test_git(args: &[&str], cwd: &Path) -> String {
+    let mut all = TEST_IDENTITY.to_vec();
+    all.extend_from_slice(args);
+    run_git(&all, Some(cwd)).unwrap()
+}
+
+/// A bare repo in `dir` and a clone of it to commit into with `test_push`.
+#[cfg(test)]
+pub(crate) fn test_remote(dir: &Path) -> (std::path::PathBuf, std::path::PathBuf) {
+    let remote = dir.join("remote.git");
+    let work = dir.join("work");
+    run_git(&["init", "--quiet", "--bare", remote.to_str().unwrap()], None).unwrap();
+    run_git(&["clone", "--quiet", remote.to_str().unwrap(), work.to_str().unwrap()], None).unwrap();
+    (remote, work)
+}
+
+/// Commit everything in `work` and push it, returning the new commit SHA.
+#[cfg(test)]
+pub(crate) fn test_push(work: &Path, message: &str) -> String {
+    test_git(&["add", "--all"], work);
+    test_git(&["commit", "--quiet", "-m", message], work);
+    test_git(&["push", "--quiet", "origin", "HEAD"], work);
+    test_git(&["rev-parse", "HEAD"], work)
+}
@@ -0,0 +1,82 @@
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+// Repo metadata and files written by Skill Studio itself, never part of a skill's
+// content. A skill at the repo root shares its folder with them.
+pub(crate) const IGNORED_FILES: [&str; 2] = ["_skills_cache.json", ".git"];
+
+/// SHA-256 over every file in `dir`, keyed by relative path, so the same
+/// skill content hashes identically wherever it lives.
+pub(crate) fn hash_dir(dir: &Path) -> Result<String, String> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files).map_err(|e| format!("Failed to hash {:?}: {}", dir, e))?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for relative in files {
+        let content = fs::read(dir.join(&relative))
+            .map_err(|e| format!("Failed to read {}: {}", relative, e))?;
+        hasher.update(relative.as_bytes());
+        hasher.update([0]);
+        hasher.update((content.len() as u64).to_le_bytes());
+        hasher.update(&content);
+    }
+
+    Ok(to_hex(&hasher.finalize()))
+}
+
+/// SHA-256 of each file in `dir`, keyed by forward-slash relative path.
+pub(crate) fn file_hashes(dir: &Path) -> Result<BTreeMap<String, String>, String> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files).map_err(|e| format!("Failed to read {:?}: {}", dir, e))?;
+
+    files
+        .into_iter()
+        .map(|relative| {
+            let content = fs::read(dir.join(&relative))
+                .map_err(|e| format!("Failed to read {}: {}", relative, e))?;
+            Ok((relative, to_hex(&Sha256::digest(&content))))
+        })
+        .collect()
+}
+
+/// Forward-slash relative paths of the files in `dir`, skipping the same files as hashing.
+pub(crate) fn list_files(dir: &Path) -> Result<Vec<String>, String> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files).map_err(|e| format!("Failed to read {:?}: {}", dir, e))?;
+    files.sort();
+    Ok(files)
+}
+
+/// Short stable key for a string, e.g. to name a cache file after a URL.
+pub(crate) fn hash_key(value: &str) -> String {
+    to_hex(&Sha256::digest(value.as_bytes())[..8])
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<String>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if IGNORED_FILES.contains(&name.as_str()) {
+            continue;
+        }
+
+        let path = entry.path();
+        if entry.file_type()?.is_symlink() && path.is_dir() && dir.canonicalize()?.starts_with(path.canonicalize()?) {
+            continue; // Symlink back to an ancestor
+        }
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            // Forward slashes keep hashes stable across platforms
+            files.push(relative.to_string_lossy().replace('\\', "/"));
+        }
+    }
+    Ok(())
+}
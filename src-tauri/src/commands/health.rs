@@ -0,0 +1,323 @@
+use super::bulk::{run_batch, Progress};
+use super::catalog::{load_merged_catalog, parse_catalog};
+use super::git::sparse_skill_clone;
+use super::paths::{unique_staging_path, SkillName};
+use super::skills::{chrono_now, scan_repo_dir};
+use crate::models::{CatalogHealthReport, CatalogRepo, HealthSummary, RepoHealth};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub(crate) const DEFAULT_STALE_DAYS: u64 = 365;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Where catalog repos are checked: GitHub itself, or local stand-ins in tests.
+struct RepoHost {
+    page_base: String, // Repo pages are "<page_base>/owner/repo"
+    git_base: String,  // Clone URLs are "<git_base>/owner/repo.git"
+}
+
+impl RepoHost {
+    fn github() -> Self {
+        RepoHost {
+            page_base: "https://github.com".to_string(),
+            git_base: "https://github.com".to_string(),
+        }
+    }
+}
+
+/// Repos of the catalog file at `path`, or of every enabled catalog source.
+pub(crate) fn catalog_repos(path: Option<&str>) -> Result<Vec<CatalogRepo>, String> {
+    match path {
+        Some(path) => {
+            let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+            Ok(parse_catalog(&content)?.repos)
+        }
+        None => Ok(load_merged_catalog().0.into_iter().map(|r| r.repo).collect()),
+    }
+}
+
+// "owner/repo" from a repo page URL such as a redirect's Location header
+fn repo_from_url(url: &str, page_base: &str) -> Option<String> {
+    let path = url.strip_prefix(page_base).unwrap_or(url);
+    let mut parts = path.trim_start_matches('/').split('/');
+    let owner = parts.next().filter(|s| !s.is_empty())?;
+    let repo = parts.next().filter(|s| !s.is_empty())?.trim_end_matches(".git");
+    Some(format!("{}/{}", owner, repo))
+}
+
+// Status of the repo's GitHub page and, for renamed or transferred repos, where it moved to
+fn page_status(host: &RepoHost, owner: &str, repo: &str) -> Result<(u16, Option<String>), String> {
+    let agent = ureq::AgentBuilder::new().redirects(0).timeout(REQUEST_TIMEOUT).build();
+    match agent.head(&format!("{}/{}/{}", host.page_base, owner, repo)).call() {
+        Ok(response) => {
+            let moved_to = response.header("location").and_then(|url| repo_from_url(url, &host.page_base));
+            Ok((response.status(), moved_to))
+        }
+        Err(ureq::Error::Status(code, _)) => Ok((code, None)),
+        Err(e) => Err(format!("Failed to reach GitHub: {}", e)),
+    }
+}
+
+// `staging` holds the sparse checkout used to count skills and is removed afterwards
+fn check_repo(host: &RepoHost, entry: &CatalogRepo, stale_days: u64, now: u64, staging: &Path) -> RepoHealth {
+    let mut health = RepoHealth {
+        repo: entry.url.clone(),
+        status: "ok".to_string(),
+        http_status: None,
+        moved_to: None,
+        skill_count: None,
+        last_commit: None,
+        last_commit_at: None,
+        days_since_commit: None,
+        suggestion: None,
+        message: None,
+    };
+    let fail = |mut health: RepoHealth, status: &str, suggestion: Option<&str>, message: String| {
+        health.status = status.to_string();
+        health.suggestion = suggestion.map(str::to_string);
+        health.message = Some(message);
+        health
+    };
+
+    let Some((owner, repo)) = entry.url.split_once('/') else {
+        return fail(health, "error", Some("remove"), "Repo is not in owner/repo format".to_string());
+    };
+    if let Err(e) = SkillName::parse(owner).and(SkillName::parse(repo)) {
+        return fail(health, "error", Some("remove"), e);
+    }
+
+    match page_status(host, owner, repo) {
+        Ok((code, location)) => {
+            health.http_status = Some(code);
+            if (300..400).contains(&code) {
+                health.moved_to = location.filter(|to| !to.eq_ignore_ascii_case(&entry.url));
+            } else if code == 404 {
+                return fail(health, "missing", Some("remove"), "Repo no longer exists or is private".to_string());
+            } else if code >= 400 {
+                return fail(health, "error", None, format!("GitHub returned HTTP {}", code));
+            }
+        }
+        Err(e) => return fail(health, "error", None, e),
+    }
+
+    // Moved repos are checked at their new location
+    let (owner, repo) = match &health.moved_to {
+        Some(to) => to.split_once('/').unwrap_or((owner, repo)),
+        None => (owner, repo),
+    };
+    let _ = fs::remove_dir_all(staging);
+    let cloned = sparse_skill_clone(&format!("{}/{}/{}.git", host.git_base, owner, repo), staging);
+    if let Ok((sha, committed_at)) = &cloned {
+        let skills = scan_repo_dir(staging, owner, repo, entry.skills_path.as_deref(), &[]);
+        health.skill_count = Some(skills.len());
+        health.last_commit = Some(sha.clone());
+        health.last_commit_at = Some(committed_at.to_string());
+        health.days_since_commit = Some(now.saturating_sub(*committed_at) / 86_400);
+    }
+    let _ = fs::remove_dir_all(staging);
+    if let Err(e) = cloned {
+        return fail(health, "error", None, e);
+    }
+
+    if let Some(to) = health.moved_to.clone() {
+        return fail(health, "moved", Some("update-url"), format!("Repo moved to {}", to));
+    }
+    if health.skill_count == Some(0) {
+        let message = match &entry.skills_path {
+            Some(path) => format!("No skills found in {}", path),
+            None => "No skills found".to_string(),
+        };
+        return fail(health, "empty", Some("remove"), message);
+    }
+    match health.days_since_commit {
+        Some(days) if days > stale_days => {
+            let message = format!("No commits in {} days", days);
+            fail(health, "stale", Some("review"), message)
+        }
+        _ => health,
+    }
+}
+
+fn summarize(repos: &[RepoHealth]) -> HealthSummary {
+    let count = |status: &str| repos.iter().filter(|r| r.status == status).count();
+    HealthSummary {
+        total: repos.len(),
+        ok: count("ok"),
+        moved: count("moved"),
+        missing: count("missing"),
+        empty: count("empty"),
+        stale: count("stale"),
+        errors: count("error"),
+    }
+}
+
+/// Check every repo for reachability, redirects, skill count and last commit age.
+pub(crate) fn check_catalog(
+    app: Option<&tauri::AppHandle>,
+    repos: &[CatalogRepo],
+    stale_days: u64,
+    concurrency: Option<usize>,
+) -> CatalogHealthReport {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let host = RepoHost::github();
+    let keys: Vec<String> = repos.iter().map(|r| r.url.clone()).collect();
+    let results: Mutex<Vec<RepoHealth>> = Mutex::new(Vec::new());
+
+    let progress = Progress {
+        app,
+        operation: "health-check",
+        total: keys.len(),
+    };
+    run_batch(&progress, &keys, 0, concurrency, |key| {
+        let entry = repos.iter().find(|r| r.url == key).ok_or("Repo not in catalog")?;
+        let health = check_repo(&host, entry, stale_days, now, &unique_staging_path("health-check"));
+        let failed = health.status != "ok";
+        let message = health.message.clone();
+        if let Ok(mut results) = results.lock() {
+            results.push(health);
+        }
+        match message {
+            Some(message) if failed => Err(message),
+            _ => Ok(()),
+        }
+    });
+
+    // Catalog order, so reports are easy to compare with the catalog file
+    let mut repos_health = results.into_inner().unwrap_or_default();
+    repos_health.sort_by_key(|h| keys.iter().position(|k| *k == h.repo));
+
+    CatalogHealthReport {
+        checked_at: chrono_now(),
+        stale_days,
+        summary: summarize(&repos_health),
+        repos: repos_health,
+    }
+}
+
+/// Report dead, moved, empty and stale repos of the catalog (or of the catalog file
+/// at `catalog_path`), with a suggested fix for each.
+#[tauri::command]
+pub async fn check_catalog_health(
+    app: tauri::AppHandle,
+    catalog_path: Option<String>,
+    stale_days: Option<u64>,
+    concurrency: Option<usize>,
+) -> Result<CatalogHealthReport, String> {
+    let repos = catalog_repos(catalog_path.as_deref())?;
+    let stale_days = stale_days.unwrap_or(DEFAULT_STALE_DAYS);
+
+    tauri::async_runtime::spawn_blocking(move || check_catalog(Some(&app), &repos, stale_days, concurrency))
+        .await
+        .map_err(|e| format!("Catalog health check failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::git::{test_git, test_push, test_remote};
+    use crate::commands::paths::test_dir;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    // Answers HEAD requests like GitHub: 301 to "owner/renamed" for "owner/moved",
+    // 404 for "owner/missing" and 200 for anything else
+    fn serve_pages() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let location = format!("{}/owner/renamed", base);
+        std::thread::spawn(move || {
+            for stream in listener.incoming().filter_map(|s| s.ok()) {
+                let mut reader = BufReader::new(&stream);
+                let mut request = String::new();
+                reader.read_line(&mut request).unwrap();
+                // Drain the headers before answering
+                let mut line = String::new();
+                while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+                    line.clear();
+                }
+                let status = match request.split(' ').nth(1) {
+                    Some("/owner/moved") => format!("301 Moved Permanently\r\nLocation: {}", location),
+                    Some("/owner/missing") => "404 Not Found".to_string(),
+                    _ => "200 OK".to_string(),
+                };
+                let response = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+                (&stream).write_all(response.as_bytes()).unwrap();
+            }
+        });
+        base
+    }
+
+    // A bare repo at `<git_base>/owner/<repo>.git`, with one skill unless `empty`
+    fn bare_repo(git_base: &Path, repo: &str, empty: bool) -> u64 {
+        let dir = git_base.join("work").join(repo);
+        let (remote, work) = test_remote(&dir);
+        let content = if empty { "docs/README.md" } else { "skills/demo/SKILL.md" };
+        fs::create_dir_all(work.join(content).parent().unwrap()).unwrap();
+        fs::write(work.join(content), "---\nname: demo\ndescription: test\n---\n").unwrap();
+        test_push(&work, "Initial commit");
+        fs::create_dir_all(git_base.join("owner")).unwrap();
+        fs::rename(remote, git_base.join("owner").join(format!("{}.git", repo))).unwrap();
+        let log = test_git(&["log", "-1", "--format=%ct"], &work);
+        log.trim().parse().unwrap()
+    }
+
+    fn check(host: &RepoHost, dir: &Path, repo: &str, now: u64) -> RepoHealth {
+        let entry: CatalogRepo = serde_json::from_value(serde_json::json!({ "url": format!("owner/{}", repo) })).unwrap();
+        check_repo(host, &entry, 30, now, &dir.join("staging"))
+    }
+
+    #[test]
+    fn reports_ok_stale_and_empty_repos() {
+        let dir = test_dir("health-repos");
+        let host = RepoHost {
+            page_base: serve_pages(),
+            git_base: dir.to_string_lossy().to_string(),
+        };
+        let committed_at = bare_repo(&dir, "skills", false);
+        bare_repo(&dir, "empty", true);
+
+        let ok = check(&host, &dir, "skills", committed_at);
+        assert_eq!(ok.status, "ok", "{:?}", ok.message);
+        assert_eq!((ok.http_status, ok.skill_count, ok.days_since_commit), (Some(200), Some(1), Some(0)));
+        assert!(!dir.join("staging").exists());
+
+        let stale = check(&host, &dir, "skills", committed_at + 31 * 86_400);
+        assert_eq!((stale.status.as_str(), stale.suggestion.as_deref()), ("stale", Some("review")));
+
+        let empty = check(&host, &dir, "empty", committed_at);
+        assert_eq!((empty.status.as_str(), empty.suggestion.as_deref()), ("empty", Some("remove")));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn reports_moved_and_missing_repos() {
+        let dir = test_dir("health-moved");
+        let host = RepoHost {
+            page_base: serve_pages(),
+            git_base: dir.to_string_lossy().to_string(),
+        };
+        let committed_at = bare_repo(&dir, "renamed", false);
+
+        // Moved repos are counted at their new location
+        let moved = check(&host, &dir, "moved", committed_at);
+        assert_eq!(moved.status, "moved", "{:?}", moved.message);
+        assert_eq!(moved.moved_to.as_deref(), Some("owner/renamed"));
+        assert_eq!((moved.http_status, moved.skill_count), (Some(301), Some(1)));
+        assert_eq!(moved.suggestion.as_deref(), Some("update-url"));
+
+        let missing = check(&host, &dir, "missing", committed_at);
+        assert_eq!((missing.status.as_str(), missing.http_status), ("missing", Some(404)));
+        assert_eq!(missing.skill_count, None);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn reads_moved_repo_from_location() {
+        let base = "https://github.com";
+        assert_eq!(repo_from_url("https://github.com/new/name", base).as_deref(), Some("new/name"));
+        assert_eq!(repo_from_url("/new/name.git", base).as_deref(), Some("new/name"));
+        assert_eq!(repo_from_url("https://github.com/new", base), None);
+    }
+}
@@ -1,79 +1,294 @@
+use super::agents::{selected_targets, InstallTarget};
+use super::catalog::{load_merged_catalog, MergedRepo};
+use super::dependencies::{installed_dependents, missing_dependencies};
+use super::hashing::{hash_dir, IGNORED_FILES};
+use super::paths::{
+    ensure_within, get_data_path, get_repos_path, resolve_install_path, resolve_repo_path, resolve_skill_source,
+    SkillName,
+};
+use super::settings::load_settings;
+use super::skills::{collect_all_skills, fetch_repo_to_cache, load_fetched_repos, scan_skills_in};
+use super::trash::{move_to_trash, purge_expired_trash};
+use crate::models::{InstallResult, Skill, TargetStatus};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
-use tauri_plugin_shell::ShellExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
 
-fn get_data_path() -> PathBuf {
-    dirs::data_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("skill-studio")
+// Held across each load-modify-save of install-records.json, which parallel installs share
+static INSTALL_RECORDS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Content hash and upstream commit of an installed copy when it was installed or
+/// last updated, so local edits can be told apart from upstream changes.
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InstallRecord {
+    pub content_hash: String,
+    pub commit: Option<String>,
+}
+
+// Keyed by the enabled install path, which stays the same while a skill is disabled
+fn get_install_records_path() -> PathBuf {
+    get_data_path().join("install-records.json")
+}
+
+pub(crate) fn load_install_records() -> HashMap<String, InstallRecord> {
+    fs::read_to_string(get_install_records_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+// Commit of the fetched repo `source` was copied from; None outside the repo cache
+fn source_commit(source: &Path) -> Option<String> {
+    let relative = source.strip_prefix(get_repos_path()).ok()?;
+    let mut parts = relative.components().map(|c| c.as_os_str().to_string_lossy().to_string());
+    let repo_key = format!("{}/{}", parts.next()?, parts.next()?);
+    load_fetched_repos().commits.remove(&repo_key)
+}
+
+// Best effort: the copy is already in place, a missing record only makes the
+// next update check fall back to comparing with the repo cache
+fn record_installs(source: &Path, installed: &[(PathBuf, PathBuf)]) {
+    if installed.is_empty() {
+        return;
+    }
+    let commit = source_commit(source);
+    let _guard = INSTALL_RECORDS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut records = load_install_records();
+    for (key_path, dest_path) in installed {
+        if let Ok(content_hash) = hash_dir(dest_path) {
+            let record = InstallRecord {
+                content_hash,
+                commit: commit.clone(),
+            };
+            records.insert(key_path.to_string_lossy().to_string(), record);
+        }
+    }
+    if let Ok(content) = serde_json::to_string_pretty(&records) {
+        let _ = fs::create_dir_all(get_data_path());
+        let _ = fs::write(get_install_records_path(), content);
+    }
+}
+
+/// Where a skill lives inside a fetched repo.
+pub(crate) struct SkillLocation<'a> {
+    pub owner: &'a str,
+    pub repo: &'a str,
+    pub skills_path: &'a str,
+    pub skill_path: &'a str,
 }
 
-fn get_installed_skills_path() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(".claude")
-        .join("skills")
+impl<'a> SkillLocation<'a> {
+    pub(crate) fn of(skill: &'a Skill) -> Self {
+        Self {
+            owner: &skill.owner,
+            repo: &skill.repo,
+            skills_path: &skill.skills_path,
+            skill_path: &skill.path,
+        }
+    }
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn install_skill(
-    app: tauri::AppHandle,
     owner: String,
     repo: String,
     skill_name: String,
     skill_path: String,
     skills_path: String,
     method: String,
-) -> Result<String, String> {
-    if method == "copy" {
-        // Direct copy method
-        let data_path = get_data_path();
-        let repo_path = data_path.join("repos").join(&owner).join(&repo);
+    scope: Option<String>,
+) -> Result<InstallResult, String> {
+    let skill_name = SkillName::parse(&skill_name)?;
+    let targets = selected_targets(scope.as_deref())?;
 
-        let source_path = if skills_path == "." {
-            repo_path.join(&skill_path)
-        } else {
-            repo_path.join(&skills_path).join(&skill_path)
-        };
+    // Install missing dependencies first so the skill works right away
+    let all_skills = collect_all_skills();
+    let catalog = load_merged_catalog().0;
+    let (dependencies, warnings) = match all_skills.iter().find(|s| {
+        s.owner == owner && s.repo == repo && (s.path == skill_path || s.name == skill_name.as_str())
+    }) {
+        Some(skill) => missing_dependencies(skill, &all_skills),
+        None => (Vec::new(), Vec::new()),
+    };
+
+    for dependency in &dependencies {
+        install_single(
+            &SkillLocation::of(dependency),
+            &SkillName::parse(&dependency.name)?,
+            &method,
+            &targets,
+            &catalog,
+        )
+        .map_err(|e| format!("Failed to install dependency '{}': {}", dependency.name, e))?;
+    }
 
-        let dest_path = get_installed_skills_path().join(&skill_name);
+    let location = SkillLocation {
+        owner: &owner,
+        repo: &repo,
+        skills_path: &skills_path,
+        skill_path: &skill_path,
+    };
+    let mut result = install_single(&location, &skill_name, &method, &targets, &catalog)?;
+    result.dependencies = dependencies.into_iter().map(|d| d.name).collect();
+    result.warnings = warnings;
+    Ok(result)
+}
 
-        // Create parent directory if needed
-        if let Some(parent) = dest_path.parent() {
-            fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create directory: {}", e))?;
+pub(crate) fn install_single(
+    location: &SkillLocation<'_>,
+    skill_name: &SkillName,
+    method: &str,
+    targets: &[InstallTarget],
+    catalog: &[MergedRepo],
+) -> Result<InstallResult, String> {
+    if method == "copy" {
+        // Direct copy method; skills listed from the index are fetched on first install
+        if !resolve_repo_path(location.owner, location.repo)?.exists() {
+            fetch_repo_to_cache(location.owner, location.repo, catalog)?;
         }
+        let source_path = resolve_skill_source(
+            location.owner,
+            location.repo,
+            location.skills_path,
+            location.skill_path,
+        )?;
+        return install_from_source(&source_path, skill_name, targets);
+    }
 
-        // Copy the skill directory
-        copy_dir_recursive(&source_path, &dest_path)
-            .map_err(|e| format!("Failed to copy skill: {}", e))?;
+    // Native install (default), with the skills CLI as an opt-in fallback
+    match install_native(location, skill_name, targets, catalog) {
+        Ok(result) => Ok(result),
+        Err(e) if load_settings().npx_fallback => {
+            install_with_npx(location.owner, location.repo, skill_name).map_err(|npx_error| format!("{} (npx fallback failed: {})", e, npx_error))
+        }
+        Err(e) => Err(e),
+    }
+}
 
-        Ok(format!("Skill '{}' installed via direct copy", skill_name))
+// Resolve the skill from the repo cache (fetching the repo on first use)
+// and place it in the agent skills directories, like `npx skills add` does
+fn install_native(
+    location: &SkillLocation<'_>,
+    skill_name: &SkillName,
+    targets: &[InstallTarget],
+    catalog: &[MergedRepo],
+) -> Result<InstallResult, String> {
+    let (owner, repo) = (location.owner, location.repo);
+    let repo_path = resolve_repo_path(owner, repo)?;
+    let skills = if repo_path.exists() {
+        scan_skills_in(&repo_path, owner, repo, &[], catalog)
     } else {
-        // npx method (default)
-        let shell = app.shell();
-
-        let output = shell
-            .command("npx")
-            .args([
-                "skills",
-                "add",
-                &format!("{}/{}", owner, repo),
-                &format!("--skill={}", skill_name),
-            ])
-            .output()
-            .await
-            .map_err(|e| format!("Failed to run npx: {}", e))?;
-
-        if output.status.success() {
-            Ok(String::from_utf8_lossy(&output.stdout).to_string())
-        } else {
-            Err(String::from_utf8_lossy(&output.stderr).to_string())
+        fetch_repo_to_cache(owner, repo, catalog)?
+    };
+
+    let skill = skills
+        .iter()
+        .find(|s| s.path == location.skill_path)
+        .or_else(|| skills.iter().find(|s| s.name == skill_name.as_str()))
+        .ok_or_else(|| format!("Skill '{}' not found in {}/{}", skill_name, owner, repo))?;
+
+    let source_path = resolve_skill_source(owner, repo, &skill.skills_path, &skill.path)?;
+    let mut result = install_from_source(&source_path, skill_name, targets)?;
+    result.method = "native".to_string();
+    Ok(result)
+}
+
+fn install_with_npx(owner: &str, repo: &str, skill_name: &SkillName) -> Result<InstallResult, String> {
+    SkillName::parse(owner)?;
+    SkillName::parse(repo)?;
+
+    let output = Command::new("npx")
+        .args([
+            "skills",
+            "add",
+            &format!("{}/{}", owner, repo),
+            &format!("--skill={}", skill_name),
+        ])
+        .output()
+        .map_err(|e| format!("Failed to run npx: {}", e))?;
+
+    if output.status.success() {
+        Ok(InstallResult {
+            skill_name: skill_name.to_string(),
+            method: "npx".to_string(),
+            targets: Vec::new(),
+            files_written: Vec::new(),
+            dependencies: Vec::new(),
+            warnings: Vec::new(),
+            output: Some(String::from_utf8_lossy(&output.stdout).to_string()),
+        })
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).to_string())
+    }
+}
+
+/// Copy an already resolved skill folder into every target. Fails only if no target succeeded.
+pub(crate) fn install_from_source(
+    source_path: &Path,
+    skill_name: &SkillName,
+    targets: &[InstallTarget],
+) -> Result<InstallResult, String> {
+    let mut statuses = Vec::new();
+    let mut written = Vec::new();
+    let mut installed = Vec::new();
+
+    for target in targets {
+        let result = resolve_install_path(&target.root, skill_name).and_then(|dest_path| {
+            // Copy the skill directory
+            copy_dir_recursive(source_path, &dest_path, &mut written)
+                .map_err(|e| format!("Failed to copy skill: {}", e))
+                .map(|_| dest_path)
+        });
+        if let Ok(dest_path) = &result {
+            installed.push((dest_path.clone(), dest_path.clone()));
         }
+        statuses.push(target_status(target, skill_name, result));
     }
+    record_installs(source_path, &installed);
+
+    if !statuses.iter().any(|s| s.success) {
+        let errors: Vec<String> = statuses.into_iter().filter_map(|s| s.error).collect();
+        return Err(errors.join("; "));
+    }
+
+    Ok(InstallResult {
+        skill_name: skill_name.to_string(),
+        method: "copy".to_string(),
+        targets: statuses,
+        files_written: written.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+        dependencies: Vec::new(),
+        warnings: Vec::new(),
+        output: None,
+    })
+}
+
+pub(crate) fn target_status(
+    target: &InstallTarget,
+    skill_name: &SkillName,
+    result: Result<PathBuf, String>,
+) -> TargetStatus {
+    let fallback = target.root.join(skill_name);
+    TargetStatus {
+        agent: target.agent.to_string(),
+        scope: target.scope.to_string(),
+        path: result.as_ref().unwrap_or(&fallback).to_string_lossy().to_string(),
+        success: result.is_ok(),
+        error: result.err(),
+    }
+}
+
+/// Copy a skill folder to `dst` with the same symlink checks as installs, returning files written.
+pub(crate) fn copy_skill_dir(src: &Path, dst: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut written = Vec::new();
+    copy_dir_recursive(src, dst, &mut written).map_err(|e| format!("Failed to copy skill: {}", e))?;
+    Ok(written)
 }
 
-fn copy_dir_recursive(src: &PathBuf, dst: &PathBuf) -> std::io::Result<()> {
+fn copy_dir_recursive(src: &Path, dst: &Path, written: &mut Vec<PathBuf>) -> std::io::Result<()> {
     if !src.exists() {
         return Err(std::io::Error::new(
             std::io::ErrorKind::NotFound,
@@ -81,17 +296,45 @@ fn copy_dir_recursive(src: &PathBuf, dst: &PathBuf) -> std::io::Result<()> {
         ));
     }
 
+    let root = src.canonicalize()?;
+    copy_dir_within(&root, &root, dst, written)
+}
+
+// Copies `src` into `dst`, following symlinks only while they resolve inside `root`
+// and skipping the same files `hash_dir` ignores
+fn copy_dir_within(root: &Path, src: &Path, dst: &Path, written: &mut Vec<PathBuf>) -> std::io::Result<()> {
     fs::create_dir_all(dst)?;
 
     for entry in fs::read_dir(src)? {
         let entry = entry?;
-        let src_path = entry.path();
+        if IGNORED_FILES.contains(&entry.file_name().to_string_lossy().as_ref()) {
+            continue;
+        }
+        let mut src_path = entry.path();
         let dst_path = dst.join(entry.file_name());
 
+        if entry.file_type()?.is_symlink() {
+            let target = src_path.canonicalize()?;
+            if !target.starts_with(root) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    format!("Symlink {:?} points outside the skill folder", src_path),
+                ));
+            }
+            if target.is_dir() && src.starts_with(&target) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Symlink {:?} creates a cycle", src_path),
+                ));
+            }
+            src_path = target;
+        }
+
         if src_path.is_dir() {
-            copy_dir_recursive(&src_path, &dst_path)?;
+            copy_dir_within(root, &src_path, &dst_path, written)?;
         } else {
             fs::copy(&src_path, &dst_path)?;
+            written.push(dst_path);
         }
     }
 
@@ -99,21 +342,189 @@ fn copy_dir_recursive(src: &PathBuf, dst: &PathBuf) -> std::io::Result<()> {
 }
 
 #[tauri::command]
-pub async fn uninstall_skill(skill_name: String) -> Result<(), String> {
-    let installed_path = get_installed_skills_path().join(&skill_name);
-
-    if installed_path.exists() {
-        if installed_path.is_symlink() {
-            fs::remove_file(&installed_path)
-                .map_err(|e| format!("Failed to remove symlink: {}", e))?;
-        } else if installed_path.is_dir() {
-            fs::remove_dir_all(&installed_path)
-                .map_err(|e| format!("Failed to remove directory: {}", e))?;
-        } else {
-            fs::remove_file(&installed_path)
-                .map_err(|e| format!("Failed to remove file: {}", e))?;
+pub async fn uninstall_skill(
+    skill_name: String,
+    force: Option<bool>,
+    scope: Option<String>,
+) -> Result<Vec<TargetStatus>, String> {
+    let skill_name = SkillName::parse(&skill_name)?;
+    let targets = selected_targets(scope.as_deref())?;
+
+    if !force.unwrap_or(false) {
+        let dependents = installed_dependents(skill_name.as_str(), &collect_all_skills());
+        if !dependents.is_empty() {
+            return Err(format!(
+                "Skill '{}' is required by {}",
+                skill_name,
+                dependents.join(", ")
+            ));
+        }
+    }
+
+    purge_expired_trash();
+    Ok(remove_from_targets(&skill_name, &targets))
+}
+
+/// Replace each installed copy of `skill_name` (enabled or disabled) with `source_path`,
+/// moving the old folder to the trash first so no stale files are left behind.
+pub(crate) fn replace_installed(
+    source_path: &Path,
+    skill_name: &SkillName,
+    targets: &[InstallTarget],
+) -> Result<InstallResult, String> {
+    let mut statuses = Vec::new();
+    let mut written = Vec::new();
+    let mut installed = Vec::new();
+
+    for target in targets {
+        for root in [target.root.clone(), target.disabled_root()] {
+            if root.join(skill_name).symlink_metadata().is_err() {
+                continue;
+            }
+            let result = remove_installed(target, &root, skill_name)
+                .and_then(|_| resolve_install_path(&root, skill_name))
+                .and_then(|dest_path| {
+                    copy_dir_recursive(source_path, &dest_path, &mut written)
+                        .map_err(|e| format!("Failed to copy skill: {}", e))
+                        .map(|_| dest_path)
+                });
+            if let Ok(dest_path) = &result {
+                installed.push((target.root.join(skill_name), dest_path.clone()));
+            }
+            statuses.push(target_status(target, skill_name, result));
+        }
+    }
+    record_installs(source_path, &installed);
+
+    if statuses.is_empty() {
+        return Err(format!("Skill '{}' is not installed", skill_name));
+    }
+    if !statuses.iter().any(|s| s.success) {
+        let errors: Vec<String> = statuses.into_iter().filter_map(|s| s.error).collect();
+        return Err(errors.join("; "));
+    }
+
+    Ok(InstallResult {
+        skill_name: skill_name.to_string(),
+        method: "copy".to_string(),
+        targets: statuses,
+        files_written: written.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+        dependencies: Vec::new(),
+        warnings: Vec::new(),
+        output: None,
+    })
+}
+
+/// Remove a skill, enabled or disabled, from each target that has it.
+pub(crate) fn remove_from_targets(skill_name: &SkillName, targets: &[InstallTarget]) -> Vec<TargetStatus> {
+    let mut statuses = Vec::new();
+
+    for target in targets {
+        for root in [target.root.clone(), target.disabled_root()] {
+            if root.join(skill_name).symlink_metadata().is_err() {
+                continue;
+            }
+            let result = remove_installed(target, &root, skill_name).map(|_| root.join(skill_name));
+            statuses.push(target_status(target, skill_name, result));
         }
     }
 
+    statuses
+}
+
+// Symlinks are just unlinked; real folders go to the trash so local edits survive
+fn remove_installed(target: &InstallTarget, root: &Path, skill_name: &SkillName) -> Result<(), String> {
+    let installed_path = root.join(skill_name);
+
+    if installed_path.is_symlink() {
+        fs::remove_file(&installed_path)
+            .map_err(|e| format!("Failed to remove symlink: {}", e))?;
+    } else if installed_path.exists() {
+        ensure_within(root, &installed_path)?;
+        move_to_trash(target, &installed_path, skill_name)?;
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::paths::test_dir;
+    use crate::commands::skills::scan_repo_dir;
+
+    fn skill_folder(dir: &Path) -> PathBuf {
+        let skill = dir.join("skill");
+        fs::create_dir_all(skill.join("scripts")).unwrap();
+        fs::write(skill.join("SKILL.md"), "---\nname: skill\ndescription: test\n---\n").unwrap();
+        fs::write(skill.join("scripts/run.sh"), "echo hi\n").unwrap();
+        skill
+    }
+
+    #[test]
+    fn copies_skill_folder() {
+        let dir = test_dir("copy");
+        let skill = skill_folder(&dir);
+        let written = copy_skill_dir(&skill, &dir.join("dest")).unwrap();
+        assert_eq!(written.len(), 2);
+        assert!(dir.join("dest/scripts/run.sh").is_file());
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn follows_symlinks_inside_the_skill() {
+        let dir = test_dir("copy-inner-link");
+        let skill = skill_folder(&dir);
+        std::os::unix::fs::symlink(skill.join("scripts/run.sh"), skill.join("run.sh")).unwrap();
+        copy_skill_dir(&skill, &dir.join("dest")).unwrap();
+        assert!(dir.join("dest/run.sh").is_file());
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_symlink_outside_the_skill() {
+        let dir = test_dir("copy-escape");
+        let skill = skill_folder(&dir);
+        fs::write(dir.join("secret.txt"), "secret").unwrap();
+        std::os::unix::fs::symlink(dir.join("secret.txt"), skill.join("secret.txt")).unwrap();
+
+        let error = copy_skill_dir(&skill, &dir.join("dest")).unwrap_err();
+        assert!(error.contains("outside the skill folder"), "{}", error);
+        assert!(!dir.join("dest/secret.txt").exists());
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_symlink_cycle() {
+        let dir = test_dir("copy-cycle");
+        let skill = skill_folder(&dir);
+        std::os::unix::fs::symlink(&skill, skill.join("scripts/loop")).unwrap();
+
+        let error = copy_skill_dir(&skill, &dir.join("dest")).unwrap_err();
+        assert!(error.contains("creates a cycle"), "{}", error);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn root_skill_copy_leaves_out_repo_metadata() {
+        let dir = test_dir("copy-root-skill");
+        let repo = dir.join("repo");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::write(repo.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::write(repo.join("SKILL.md"), "---\nname: solo\ndescription: test\n---\n").unwrap();
+        fs::write(repo.join("_skills_cache.json"), "[]").unwrap();
+
+        let skills = scan_repo_dir(&repo, "owner", "solo-repo", None, &[]);
+        assert_eq!(skills.len(), 1);
+        let source = repo.join(&skills[0].skills_path).join(&skills[0].path);
+        let written = copy_skill_dir(&source, &dir.join("dest")).unwrap();
+
+        assert_eq!(written, vec![dir.join("dest/SKILL.md")]);
+        assert!(!dir.join("dest/.git").exists());
+        assert!(!dir.join("dest/_skills_cache.json").exists());
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,263 @@
+use super::agents::{selected_targets, user_targets, InstallTarget};
+use super::hashing::hash_dir;
+use super::skills::{cached_skill_dir, collect_all_skills, find_skill_file, parse_frontmatter, UNKNOWN_NAME};
+use crate::models::{InventoryEntry, Skill, SkillOrigin};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Total size and number of files under `dir`
+fn dir_stats(dir: &Path) -> (u64, usize) {
+    let mut size = 0;
+    let mut count = 0;
+
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                let (sub_size, sub_count) = dir_stats(&entry.path());
+                size += sub_size;
+                count += sub_count;
+            } else if let Ok(metadata) = entry.path().metadata() {
+                size += metadata.len();
+                count += 1;
+            }
+        }
+    }
+
+    (size, count)
+}
+
+/// Matches installed folders back to cached skills. Upstream copies are hashed once,
+/// the first time a folder's content has to be looked up across every cached skill.
+struct OriginMatcher<'a, F: Fn(&Skill) -> Option<PathBuf>> {
+    skills: &'a [Skill],
+    upstream_dir: F,
+    by_name: HashMap<&'a str, Vec<&'a Skill>>, // folder and frontmatter names
+    hashes: HashMap<String, Option<String>>,  // skill id -> upstream hash
+    by_hash: Option<HashMap<String, &'a Skill>>,
+}
+
+impl<'a, F: Fn(&Skill) -> Option<PathBuf>> OriginMatcher<'a, F> {
+    fn new(skills: &'a [Skill], upstream_dir: F) -> Self {
+        let mut by_name: HashMap<&str, Vec<&Skill>> = HashMap::new();
+        for skill in skills {
+            by_name.entry(skill.path.as_str()).or_default().push(skill);
+            if skill.name != skill.path && skill.name != UNKNOWN_NAME {
+                by_name.entry(skill.name.as_str()).or_default().push(skill);
+            }
+        }
+        Self {
+            skills,
+            upstream_dir,
+            by_name,
+            hashes: HashMap::new(),
+            by_hash: None,
+        }
+    }
+
+    fn upstream_hash(&mut self, skill: &Skill) -> Option<String> {
+        let upstream_dir = &self.upstream_dir;
+        self.hashes
+            .entry(skill.id.clone())
+            .or_insert_with(|| upstream_dir(skill).and_then(|dir| hash_dir(&dir).ok()))
+            .clone()
+    }
+
+    fn hash_match(&mut self, hash: &str) -> Option<&'a Skill> {
+        if self.by_hash.is_none() {
+            let mut by_hash = HashMap::new();
+            for skill in self.skills {
+                if let Some(upstream) = self.upstream_hash(skill) {
+                    by_hash.entry(upstream).or_insert(skill);
+                }
+            }
+            self.by_hash = Some(by_hash);
+        }
+        self.by_hash.as_ref().and_then(|by_hash| by_hash.get(hash).copied())
+    }
+
+    fn find(&mut self, folder: &str, name: Option<&str>, hash: Option<&str>) -> Option<SkillOrigin> {
+        let name = name.filter(|n| *n != UNKNOWN_NAME);
+        let mut by_name: Vec<&'a Skill> = Vec::new();
+        for key in [Some(folder), name].into_iter().flatten() {
+            for skill in self.by_name.get(key).into_iter().flatten() {
+                if !by_name.iter().any(|s| s.id == skill.id) {
+                    by_name.push(skill);
+                }
+            }
+        }
+
+        // Exact content match among same-named skills, then across every cached skill
+        if let Some(hash) = hash {
+            let same_named = by_name
+                .iter()
+                .copied()
+                .find(|skill| self.upstream_hash(skill).as_deref() == Some(hash));
+            if let Some(skill) = same_named.or_else(|| self.hash_match(hash)) {
+                return Some(SkillOrigin {
+                    skill_id: skill.id.clone(),
+                    matched_by: "hash".to_string(),
+                    is_modified: Some(false),
+                });
+            }
+        }
+
+        // Without an upstream copy to compare against, whether it changed is unknown
+        let skill = *by_name.first()?;
+        let upstream = self.upstream_hash(skill);
+        Some(SkillOrigin {
+            skill_id: skill.id.clone(),
+            matched_by: "name".to_string(),
+            is_modified: upstream.and(hash).map(|_| true),
+        })
+    }
+}
+
+fn inventory_entry(
+    target: &InstallTarget,
+    path: &Path,
+    is_disabled: bool,
+    matcher: &mut OriginMatcher<impl Fn(&Skill) -> Option<PathBuf>>,
+) -> InventoryEntry {
+    let folder = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let frontmatter = find_skill_file(path)
+        .and_then(|file| fs::read_to_string(file).ok())
+        .map(|content| parse_frontmatter(&content));
+    let (size_bytes, file_count) = dir_stats(path);
+    let content_hash = hash_dir(path).ok();
+
+    let name = frontmatter.as_ref().map(|f| f.name.clone());
+    let origin = matcher.find(&folder, name.as_deref(), content_hash.as_deref());
+
+    InventoryEntry {
+        agent: target.agent.to_string(),
+        scope: target.scope.to_string(),
+        path: path.to_string_lossy().to_string(),
+        is_symlink: path.is_symlink(),
+        is_disabled,
+        is_valid: frontmatter.is_some(),
+        description: frontmatter.as_ref().map(|f| f.description.clone()),
+        dependencies: frontmatter.map(|f| f.dependencies).unwrap_or_default(),
+        name,
+        size_bytes,
+        file_count,
+        content_hash,
+        is_orphaned: origin.is_none(),
+        origin,
+        folder,
+    }
+}
+
+#[tauri::command]
+pub async fn get_skill_inventory() -> Result<Vec<InventoryEntry>, String> {
+    let skills = collect_all_skills();
+    let mut matcher = OriginMatcher::new(&skills, |skill| cached_skill_dir(skill).ok());
+
+    let mut targets = user_targets();
+    if let Ok(project_targets) = selected_targets(Some("project")) {
+        targets.extend(project_targets);
+    }
+
+    let mut entries = Vec::new();
+    for target in &targets {
+        for (root, is_disabled) in [(target.root.clone(), false), (target.disabled_root(), true)] {
+            let Ok(dir_entries) = fs::read_dir(&root) else {
+                continue;
+            };
+            for entry in dir_entries.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                if path.is_dir() {
+                    entries.push(inventory_entry(target, &path, is_disabled, &mut matcher));
+                }
+            }
+        }
+    }
+
+    entries.sort_by(|a, b| a.folder.cmp(&b.folder).then(a.agent.cmp(&b.agent)));
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::paths::test_dir;
+
+    fn skill(id: &str, name: &str) -> Skill {
+        let parts: Vec<&str> = id.split('/').collect();
+        Skill {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            owner: parts[0].to_string(),
+            repo: parts[1].to_string(),
+            skills_path: "skills".to_string(),
+            path: parts[2].to_string(),
+            content: None,
+            is_installed: true,
+            is_fetched: true,
+            dependencies: Vec::new(),
+            is_disabled: false,
+            installs: None,
+            recent_installs: None,
+        }
+    }
+
+    // Upstream copies live under `dir/<skill id>`, skills without one are not cached
+    fn write(dir: &Path, content: &str) -> String {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("SKILL.md"), content).unwrap();
+        hash_dir(dir).unwrap()
+    }
+
+    fn upstream(dir: &Path) -> impl Fn(&Skill) -> Option<PathBuf> + '_ {
+        move |skill| Some(dir.join(&skill.id)).filter(|d| d.exists())
+    }
+
+    #[test]
+    fn matches_by_hash_anywhere_in_the_cache() {
+        let dir = test_dir("inventory-hash");
+        let skills = [skill("a/r/pdf", "pdf"), skill("b/r/docs", "docs")];
+        write(&dir.join("a/r/pdf"), "pdf");
+        let hash = write(&dir.join("b/r/docs"), "docs");
+
+        let mut matcher = OriginMatcher::new(&skills, upstream(&dir));
+        let origin = matcher.find("renamed", Some("renamed"), Some(&hash)).unwrap();
+        assert_eq!(origin.skill_id, "b/r/docs");
+        assert_eq!(origin.matched_by, "hash");
+        assert_eq!(origin.is_modified, Some(false));
+    }
+
+    #[test]
+    fn name_matches_are_modified_only_against_an_upstream_copy() {
+        let dir = test_dir("inventory-name");
+        let skills = [skill("a/r/pdf", "pdf"), skill("b/r/docs", "docs")];
+        write(&dir.join("a/r/pdf"), "pdf");
+
+        let mut matcher = OriginMatcher::new(&skills, upstream(&dir));
+        let edited = matcher.find("pdf", None, Some("edited")).unwrap();
+        assert_eq!(edited.skill_id, "a/r/pdf");
+        assert_eq!(edited.matched_by, "name");
+        assert_eq!(edited.is_modified, Some(true));
+
+        // The docs repo is not fetched, so there is nothing to compare with
+        let unknown = matcher.find("my-docs", Some("docs"), Some("edited")).unwrap();
+        assert_eq!(unknown.skill_id, "b/r/docs");
+        assert_eq!(unknown.is_modified, None);
+    }
+
+    #[test]
+    fn placeholder_names_do_not_match() {
+        let dir = test_dir("inventory-unknown");
+        let skills = [skill("a/r/broken", UNKNOWN_NAME)];
+
+        let mut matcher = OriginMatcher::new(&skills, upstream(&dir));
+        assert!(matcher.find("other", Some(UNKNOWN_NAME), Some("hash")).is_none());
+        assert!(matcher.find("broken", Some(UNKNOWN_NAME), None).is_some());
+    }
+}
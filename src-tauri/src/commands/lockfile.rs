@@ -0,0 +1,301 @@
+use super::agents::{agent_target, all_user_targets, InstallTarget};
+use super::git::{github_url, shallow_clone};
+use super::hashing::hash_dir;
+use super::install::{install_from_source, load_install_records, replace_installed, InstallRecord};
+use super::paths::{parse_relative_path, resolve_pinned_repo_path, SkillName};
+use super::skills::{chrono_now, collect_all_skills};
+use crate::models::{LockedSkill, Lockfile, Skill, LOCKFILE_VERSION};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+fn split_repo_url(url: &str) -> Option<(&str, &str)> {
+    let rest = url.strip_prefix("https://github.com/")?;
+    rest.trim_end_matches(".git").split_once('/')
+}
+
+/// Lock entries for installed skills, one per agent a skill is installed in. Installs
+/// without a recorded commit, or edited since they were installed, cannot be restored
+/// from their upstream commit and are reported instead.
+#[derive(Default)]
+struct LockedInstalls {
+    locked: Vec<LockedSkill>,
+    unrecorded: Vec<String>,
+    modified: Vec<String>,
+    not_found: Vec<String>,
+}
+
+impl LockedInstalls {
+    fn add(
+        &mut self,
+        skill: &Skill,
+        targets: &[InstallTarget],
+        records: &HashMap<String, InstallRecord>,
+    ) -> Result<(), String> {
+        let relative = parse_relative_path(&skill.skills_path)?.join(parse_relative_path(&skill.path)?);
+        let mut names = vec![skill.name.as_str()];
+        if skill.path != skill.name {
+            names.push(&skill.path);
+        }
+
+        let mut found = false;
+        for target in targets {
+            let Some((name, installed_dir)) = names
+                .iter()
+                .filter(|name| SkillName::parse(name).is_ok())
+                .map(|name| (*name, target.root.join(name)))
+                .find(|(_, dir)| dir.exists())
+            else {
+                continue;
+            };
+            found = true;
+
+            let label = format!("{} ({})", skill.name, target.agent);
+            let Some((record, commit)) = records
+                .get(installed_dir.to_string_lossy().as_ref())
+                .and_then(|r| Some((r, r.commit.clone()?)))
+            else {
+                self.unrecorded.push(label);
+                continue;
+            };
+            if hash_dir(&installed_dir)? != record.content_hash {
+                self.modified.push(label);
+                continue;
+            }
+
+            self.locked.push(LockedSkill {
+                id: skill.id.clone(),
+                source: github_url(&skill.owner, &skill.repo),
+                commit,
+                path: relative.to_string_lossy().replace('\\', "/"),
+                content_hash: record.content_hash.clone(),
+                target: target.agent.to_string(),
+                install_name: name.to_string(),
+            });
+        }
+
+        if !found {
+            self.not_found.push(skill.name.clone());
+        }
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub async fn export_lockfile(path: String) -> Result<String, String> {
+    let targets = all_user_targets();
+    let records = load_install_records();
+    let mut installs = LockedInstalls::default();
+
+    for skill in collect_all_skills().into_iter().filter(|s| s.is_installed) {
+        installs.add(&skill, &targets, &records)?;
+    }
+
+    installs.locked.sort_by(|a, b| (&a.id, &a.target).cmp(&(&b.id, &b.target)));
+    let lockfile = Lockfile {
+        version: LOCKFILE_VERSION,
+        generated_at: chrono_now(),
+        skills: installs.locked,
+    };
+
+    let content = serde_json::to_string_pretty(&lockfile)
+        .map_err(|e| format!("Failed to serialize lockfile: {}", e))?;
+    fs::write(PathBuf::from(&path), content)
+        .map_err(|e| format!("Failed to write lockfile: {}", e))?;
+
+    let mut message = format!("Exported {} skills to {}", lockfile.skills.len(), path);
+    if !installs.unrecorded.is_empty() {
+        message.push_str(&format!(
+            " (skipped {}: reinstall them to record the commit)",
+            installs.unrecorded.join(", ")
+        ));
+    }
+    if !installs.modified.is_empty() {
+        message.push_str(&format!(
+            " (skipped {}: modified since they were installed)",
+            installs.modified.join(", ")
+        ));
+    }
+    if !installs.not_found.is_empty() {
+        message.push_str(&format!(
+            " (skipped {}: installed folder not found)",
+            installs.not_found.join(", ")
+        ));
+    }
+    Ok(message)
+}
+
+/// Checkout of `source` at the full commit SHA `commit`, cloned on first use.
+pub(crate) fn commit_checkout(source: &str, owner: &str, repo: &str, commit: &str) -> Result<PathBuf, String> {
+    let checkout = resolve_pinned_repo_path(owner, repo, commit)?;
+    // Checkouts are only kept once their commit matched, so an existing one is trusted
+    if !checkout.exists() {
+        let cloned = shallow_clone(source, &checkout, Some(commit)).and_then(|cloned| {
+            if cloned.sha == commit {
+                Ok(())
+            } else {
+                Err(format!("Commit mismatch (expected {}, got {})", commit, cloned.sha))
+            }
+        });
+        if let Err(e) = cloned {
+            fs::remove_dir_all(&checkout).ok();
+            return Err(e);
+        }
+    }
+    Ok(checkout)
+}
+
+fn restore_locked_skill(entry: &LockedSkill) -> Result<(), String> {
+    let target = agent_target(&entry.target, None)?;
+    let (owner, repo) = split_repo_url(&entry.source)
+        .ok_or_else(|| format!("Unsupported source '{}'", entry.source))?;
+    let install_name = SkillName::parse(&entry.install_name)?;
+
+    let checkout = commit_checkout(&entry.source, owner, repo, &entry.commit)?;
+    let source = checkout.join(parse_relative_path(&entry.path)?);
+    let actual_hash = hash_dir(&source)?;
+    if actual_hash != entry.content_hash {
+        return Err(format!(
+            "Content hash mismatch (expected {}, got {})",
+            entry.content_hash, actual_hash
+        ));
+    }
+
+    // An installed copy is moved to the trash first so no stale files are left behind
+    let is_installed = [target.root.clone(), target.disabled_root()]
+        .iter()
+        .any(|root| root.join(&install_name).symlink_metadata().is_ok());
+    if is_installed {
+        replace_installed(&source, &install_name, &[target]).map(|_| ())
+    } else {
+        install_from_source(&source, &install_name, &[target]).map(|_| ())
+    }
+}
+
+#[tauri::command]
+pub async fn restore_from_lockfile(path: String) -> Result<String, String> {
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read lockfile: {}", e))?;
+    let lockfile: Lockfile = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse lockfile: {}", e))?;
+
+    if lockfile.version > LOCKFILE_VERSION {
+        return Err(format!("Unsupported lockfile version {}", lockfile.version));
+    }
+
+    let mut failures = Vec::new();
+    for entry in &lockfile.skills {
+        if let Err(e) = restore_locked_skill(entry) {
+            failures.push(format!("{} ({}): {}", entry.id, entry.target, e));
+        }
+    }
+
+    let restored = lockfile.skills.len() - failures.len();
+    if failures.is_empty() {
+        Ok(format!("Restored {} skills from {}", restored, path))
+    } else {
+        Err(format!(
+            "Restored {} of {} skills. Failed: {}",
+            restored,
+            lockfile.skills.len(),
+            failures.join("; ")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::paths::test_dir;
+    use std::path::Path;
+
+    fn skill() -> Skill {
+        Skill {
+            id: "owner/repo/demo-folder".to_string(),
+            name: "demo".to_string(),
+            description: String::new(),
+            owner: "owner".to_string(),
+            repo: "repo".to_string(),
+            skills_path: "skills".to_string(),
+            path: "demo-folder".to_string(),
+            content: None,
+            is_installed: true,
+            is_fetched: true,
+            dependencies: Vec::new(),
+            is_disabled: false,
+            installs: None,
+            recent_installs: None,
+        }
+    }
+
+    fn target(dir: &Path, agent: &'static str) -> InstallTarget {
+        InstallTarget {
+            agent,
+            scope: "user",
+            root: dir.join(agent).join("skills"),
+        }
+    }
+
+    fn install(target: &InstallTarget, name: &str) -> PathBuf {
+        let dir = target.root.join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("SKILL.md"), "---\nname: demo\n---\n").unwrap();
+        dir
+    }
+
+    fn record(records: &mut HashMap<String, InstallRecord>, dir: &Path, commit: Option<&str>) {
+        let record = InstallRecord {
+            content_hash: hash_dir(dir).unwrap(),
+            commit: commit.map(str::to_string),
+        };
+        records.insert(dir.to_string_lossy().to_string(), record);
+    }
+
+    #[test]
+    fn locks_every_agent_from_install_records() {
+        let dir = test_dir("lockfile-agents");
+        let targets = [target(&dir, "claude-code"), target(&dir, "codex"), target(&dir, "cursor")];
+        let mut records = HashMap::new();
+        let claude = install(&targets[0], "demo");
+        record(&mut records, &claude, Some("abc"));
+        let codex = install(&targets[1], "demo-folder");
+        record(&mut records, &codex, Some("def"));
+
+        let mut installs = LockedInstalls::default();
+        installs.add(&skill(), &targets, &records).unwrap();
+
+        let locked: Vec<_> = installs
+            .locked
+            .iter()
+            .map(|l| (l.target.as_str(), l.install_name.as_str(), l.commit.as_str()))
+            .collect();
+        assert_eq!(locked, [("claude-code", "demo", "abc"), ("codex", "demo-folder", "def")]);
+        assert_eq!(installs.locked[0].path, "skills/demo-folder");
+        assert_eq!(installs.locked[0].content_hash, records[claude.to_string_lossy().as_ref()].content_hash);
+        assert!(installs.unrecorded.is_empty() && installs.modified.is_empty() && installs.not_found.is_empty());
+    }
+
+    #[test]
+    fn skips_modified_and_unrecorded_installs() {
+        let dir = test_dir("lockfile-skips");
+        let targets = [target(&dir, "claude-code"), target(&dir, "codex"), target(&dir, "cursor")];
+        let mut records = HashMap::new();
+        let claude = install(&targets[0], "demo");
+        record(&mut records, &claude, Some("abc"));
+        fs::write(claude.join("notes.md"), "local edit").unwrap();
+        let codex = install(&targets[1], "demo");
+        record(&mut records, &codex, None);
+        install(&targets[2], "demo");
+
+        let mut installs = LockedInstalls::default();
+        installs.add(&skill(), &targets, &records).unwrap();
+
+        assert!(installs.locked.is_empty());
+        assert_eq!(installs.modified, ["demo (claude-code)"]);
+        assert_eq!(installs.unrecorded, ["demo (codex)", "demo (cursor)"]);
+
+        let mut installs = LockedInstalls::default();
+        installs.add(&skill(), &[target(&dir, "copilot")], &records).unwrap();
+        assert_eq!(installs.not_found, ["demo"]);
+    }
+}
@@ -0,0 +1,276 @@
+use super::catalog::load_merged_catalog;
+use super::hashing::list_files;
+use super::paths::{get_data_path, resolve_repo_path};
+use super::skills::{chrono_now, load_fetched_repos, scan_skills_in};
+use crate::models::{GitHubRepoInfo, RepoMetadata};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const GITHUB_TTL_SECS: u64 = 24 * 60 * 60;
+const API_TIMEOUT: Duration = Duration::from_secs(10);
+const LICENSE_FILES: [&str; 8] = [
+    "LICENSE", "LICENSE.md", "LICENSE.txt", "LICENCE", "LICENCE.md", "COPYING", "license", "license.md",
+];
+
+// GitHub API responses, keyed by "owner/repo"
+fn get_github_cache_path() -> PathBuf {
+    get_data_path().join("github-metadata.json")
+}
+
+fn load_github_cache() -> HashMap<String, GitHubRepoInfo> {
+    fs::read_to_string(get_github_cache_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_github_cache(cache: &HashMap<String, GitHubRepoInfo>) -> Result<(), String> {
+    fs::create_dir_all(get_data_path()).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(cache).map_err(|e| e.to_string())?;
+    fs::write(get_github_cache_path(), content).map_err(|e| format!("Failed to cache GitHub metadata: {}", e))
+}
+
+// Best-effort SPDX identifier from the text of a license file
+fn detect_license(text: &str) -> &'static str {
+    let text = text.to_lowercase();
+    let has = |needle: &str| text.contains(needle);
+
+    if has("gnu affero general public license") {
+        "AGPL-3.0"
+    } else if has("gnu lesser general public license") {
+        if has("version 3") { "LGPL-3.0" } else { "LGPL-2.1" }
+    } else if has("gnu general public license") {
+        if has("version 3") { "GPL-3.0" } else { "GPL-2.0" }
+    } else if has("apache license") && has("version 2.0") {
+        "Apache-2.0"
+    } else if has("mozilla public license") {
+        "MPL-2.0"
+    } else if has("permission is hereby granted, free of charge") {
+        "MIT"
+    } else if has("permission to use, copy, modify, and/or distribute") {
+        "ISC"
+    } else if has("redistribution and use in source and binary forms") {
+        if has("neither the name") { "BSD-3-Clause" } else { "BSD-2-Clause" }
+    } else if has("this is free and unencumbered software") {
+        "Unlicense"
+    } else if has("cc0 1.0") {
+        "CC0-1.0"
+    } else if has("creative commons attribution 4.0") {
+        "CC-BY-4.0"
+    } else {
+        "Other"
+    }
+}
+
+// Fill in what the fetched copy of the repo tells us
+fn add_local_metadata(metadata: &mut RepoMetadata, repo_path: &Path) -> Result<(), String> {
+    let repo_key = format!("{}/{}", metadata.owner, metadata.repo);
+    let fetched = load_fetched_repos();
+    metadata.fetched_at = fetched.repos.get(&repo_key).cloned();
+    metadata.last_commit = fetched.commits.get(&repo_key).cloned();
+    metadata.last_commit_at = fetched.commit_dates.get(&repo_key).cloned();
+    metadata.commit_count = fetched.history_lens.get(&repo_key).copied();
+
+    if let Some(name) = LICENSE_FILES.iter().find(|name| repo_path.join(name).is_file()) {
+        metadata.license_file = Some(name.to_string());
+        metadata.license_type = fs::read_to_string(repo_path.join(name))
+            .ok()
+            .map(|text| detect_license(&text).to_string());
+    }
+
+    let catalog = load_merged_catalog().0;
+    metadata.skill_count = scan_skills_in(repo_path, &metadata.owner, &metadata.repo, &[], &catalog).len();
+    let files = list_files(repo_path)?;
+    metadata.file_count = files.len();
+    metadata.total_size = files
+        .iter()
+        .filter_map(|file| fs::metadata(repo_path.join(file)).ok())
+        .map(|m| m.len())
+        .sum();
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct ApiRepo {
+    stargazers_count: u64,
+    forks_count: u64,
+    open_issues_count: u64,
+    #[serde(default)]
+    topics: Vec<String>,
+    license: Option<ApiLicense>,
+    #[serde(default)]
+    archived: bool,
+    default_branch: Option<String>,
+    pushed_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ApiLicense {
+    spdx_id: Option<String>,
+}
+
+fn api_get<T: serde::de::DeserializeOwned>(url: &str) -> Result<T, String> {
+    let response = ureq::get(url)
+        .timeout(API_TIMEOUT)
+        .set("Accept", "application/vnd.github+json")
+        .set("User-Agent", "skill-studio")
+        .call()
+        .map_err(|e| match e {
+            ureq::Error::Status(404, _) => "Repo not found on GitHub".to_string(),
+            ureq::Error::Status(403 | 429, _) => "GitHub API rate limit reached".to_string(),
+            ureq::Error::Status(code, _) => format!("GitHub API returned HTTP {}", code),
+            e => format!("Failed to reach GitHub: {}", e),
+        })?;
+    let body = response
+        .into_string()
+        .map_err(|e| format!("Failed to read GitHub response: {}", e))?;
+    serde_json::from_str(&body).map_err(|e| format!("Unexpected GitHub response: {}", e))
+}
+
+fn fetch_github_info(owner: &str, repo: &str) -> Result<GitHubRepoInfo, String> {
+    let url = format!("https://api.github.com/repos/{}/{}", owner, repo);
+    let api: ApiRepo = api_get(&url)?;
+
+    // Only the first page is counted; a failure here leaves the count unknown
+    let contributors = api_get::<Vec<serde_json::Value>>(&format!("{}/contributors?per_page=100&anon=1", url))
+        .ok()
+        .map(|list| list.len());
+
+    Ok(GitHubRepoInfo {
+        stars: api.stargazers_count,
+        forks: api.forks_count,
+        open_issues: api.open_issues_count,
+        topics: api.topics,
+        license: api.license.and_then(|l| l.spdx_id).filter(|id| id != "NOASSERTION"),
+        archived: api.archived,
+        default_branch: api.default_branch,
+        pushed_at: api.pushed_at,
+        contributors,
+        fetched_at: chrono_now(),
+    })
+}
+
+fn is_fresh(info: &GitHubRepoInfo) -> bool {
+    let now: u64 = chrono_now().parse().unwrap_or(0);
+    info.fetched_at
+        .parse::<u64>()
+        .is_ok_and(|fetched_at| now.saturating_sub(fetched_at) < GITHUB_TTL_SECS)
+}
+
+// Cached GitHub details, refreshed when older than the TTL. When GitHub cannot be
+// reached the stale copy is returned along with the error.
+fn github_info(owner: &str, repo: &str) -> (Option<GitHubRepoInfo>, Option<String>) {
+    let repo_key = format!("{}/{}", owner, repo);
+    let mut cache = load_github_cache();
+    let cached = cache.get(&repo_key).cloned();
+    if cached.as_ref().is_some_and(is_fresh) {
+        return (cached, None);
+    }
+
+    match fetch_github_info(owner, repo) {
+        Ok(info) => {
+            cache.insert(repo_key, info.clone());
+            let _ = save_github_cache(&cache);
+            (Some(info), None)
+        }
+        Err(e) => (cached, Some(e)),
+    }
+}
+
+pub(crate) fn repo_metadata(owner: &str, repo: &str, github: bool) -> Result<RepoMetadata, String> {
+    let repo_path = resolve_repo_path(owner, repo)?;
+    let mut metadata = RepoMetadata {
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+        is_fetched: repo_path.exists(),
+        fetched_at: None,
+        license_file: None,
+        license_type: None,
+        last_commit: None,
+        last_commit_at: None,
+        commit_count: None,
+        skill_count: 0,
+        file_count: 0,
+        total_size: 0,
+        github: None,
+        github_error: None,
+    };
+
+    if metadata.is_fetched {
+        add_local_metadata(&mut metadata, &repo_path)?;
+    }
+
+    // Without `github`, only what was cached earlier is shown and nothing is requested
+    if github {
+        (metadata.github, metadata.github_error) = github_info(owner, repo);
+    } else {
+        metadata.github = load_github_cache().remove(&format!("{}/{}", owner, repo));
+    }
+
+    Ok(metadata)
+}
+
+/// Details about a repo from its fetched copy and, when `github` is true, the GitHub API.
+#[tauri::command]
+pub async fn get_repo_metadata(owner: String, repo: String, github: Option<bool>) -> Result<RepoMetadata, String> {
+    tauri::async_runtime::spawn_blocking(move || repo_metadata(&owner, &repo, github.unwrap_or(false)))
+        .await
+        .map_err(|e| format!("Failed to read repo metadata: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_common_licenses() {
+        let cases = [
+            ("MIT License\n\nPermission is hereby granted, free of charge, to any person", "MIT"),
+            ("Apache License\nVersion 2.0, January 2004", "Apache-2.0"),
+            ("GNU GENERAL PUBLIC LICENSE\nVersion 3, 29 June 2007", "GPL-3.0"),
+            ("GNU GENERAL PUBLIC LICENSE\nVersion 2, June 1991", "GPL-2.0"),
+            // Lesser and Affero texts also mention the plain GPL
+            ("GNU LESSER GENERAL PUBLIC LICENSE\nVersion 3\n... the GNU General Public License", "LGPL-3.0"),
+            ("GNU AFFERO GENERAL PUBLIC LICENSE\nVersion 3\n... GNU General Public License", "AGPL-3.0"),
+            ("Redistribution and use in source and binary forms ... Neither the name of", "BSD-3-Clause"),
+            ("Redistribution and use in source and binary forms, with or without", "BSD-2-Clause"),
+            ("Permission to use, copy, modify, and/or distribute this software", "ISC"),
+            ("This is free and unencumbered software released into the public domain.", "Unlicense"),
+            ("All rights reserved.", "Other"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(detect_license(text), expected, "{:?}", text);
+        }
+    }
+
+    fn fetched(seconds_ago: u64) -> GitHubRepoInfo {
+        let now: u64 = chrono_now().parse().unwrap();
+        GitHubRepoInfo {
+            stars: 0,
+            forks: 0,
+            open_issues: 0,
+            topics: Vec::new(),
+            license: None,
+            archived: false,
+            default_branch: None,
+            pushed_at: None,
+            contributors: None,
+            fetched_at: (now - seconds_ago).to_string(),
+        }
+    }
+
+    #[test]
+    fn cached_info_expires_after_the_ttl() {
+        assert!(is_fresh(&fetched(0)));
+        assert!(is_fresh(&fetched(GITHUB_TTL_SECS - 60)));
+        assert!(!is_fresh(&fetched(GITHUB_TTL_SECS + 1)));
+        let unreadable = GitHubRepoInfo {
+            fetched_at: "yesterday".to_string(),
+            ..fetched(0)
+        };
+        assert!(!is_fresh(&unreadable));
+    }
+}
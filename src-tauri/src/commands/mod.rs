@@ -1,7 +1,46 @@
 pub mod skills;
 pub mod install;
 pub mod settings;
+pub mod agents;
+pub mod dependencies;
+pub mod bundles;
+pub mod lockfile;
+pub mod inventory;
+pub mod disable;
+pub mod trash;
+pub mod backup;
+pub mod bulk;
+pub mod updates;
+pub mod diff;
+pub mod authoring;
+pub mod fork;
+pub mod catalog;
+pub mod health;
+pub mod metadata;
+pub mod popularity;
+pub mod publish;
+pub(crate) mod git;
+mod hashing;
+mod paths;
 
-pub use skills::{get_all_skills, get_catalog, get_fetched_repos, get_installed_skills, fetch_repo, add_custom_repo, remove_custom_repo, get_custom_repos, get_repo_readme, get_favorites, toggle_favorite_skill, toggle_favorite_repo, reveal_skill_in_finder, get_all_repos};
+pub use skills::{get_all_skills, get_catalog, get_fetched_repos, get_installed_skills, fetch_repo, add_custom_repo, remove_custom_repo, get_custom_repos, get_repo_readme, get_favorites, toggle_favorite_skill, toggle_favorite_repo, reveal_skill_in_finder, get_all_repos, open_skill};
 pub use install::{install_skill, uninstall_skill};
 pub use settings::{get_settings, save_settings};
+pub use dependencies::{get_skill_dependencies, get_skill_dependents};
+pub use bundles::{get_bundles, save_bundle, delete_bundle, get_bundle_status, install_bundle, uninstall_bundle};
+pub use lockfile::{export_lockfile, restore_from_lockfile};
+pub use agents::get_agent_targets;
+pub use inventory::get_skill_inventory;
+pub use disable::{disable_skill, enable_skill};
+pub use trash::{list_trash, restore_skill, empty_trash};
+pub use backup::{export_backup, import_backup};
+pub use bulk::{bulk_install, bulk_uninstall, bulk_update};
+pub use updates::{check_updates, update_all};
+pub use diff::diff_skill_versions;
+pub use authoring::{list_skill_templates, create_skill};
+pub use fork::{list_forks, fork_skill, get_fork_status, merge_upstream};
+pub use catalog::{get_catalog_info, get_catalog_sources, refresh_catalog};
+pub use health::check_catalog_health;
+pub use metadata::get_repo_metadata;
+pub use popularity::{refresh_popularity, get_ranked_skills, get_popularity_info};
+pub use publish::publish_skill;
@@ -0,0 +1,212 @@
+use super::hashing::hash_key;
+use super::skills::chrono_now;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+pub(crate) fn get_data_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("skill-studio")
+}
+
+pub(crate) fn get_repos_path() -> PathBuf {
+    get_data_path().join("repos")
+}
+
+/// A fresh folder path under `<data>/<kind>`, unique across processes and threads, for work
+/// that is moved into place (or discarded) once it is complete.
+pub(crate) fn unique_staging_path(kind: &str) -> PathBuf {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    get_data_path().join(kind).join(format!(
+        "{}-{}-{}",
+        chrono_now(),
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::SeqCst)
+    ))
+}
+
+/// Workspace for skills authored locally rather than fetched from a repo.
+pub(crate) fn get_local_skills_path() -> PathBuf {
+    get_data_path().join("local-skills")
+}
+
+/// A single folder name used to identify an installed skill.
+/// Rejects anything that could escape the directory it is joined onto.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SkillName(String);
+
+impl SkillName {
+    pub(crate) fn parse(value: &str) -> Result<Self, String> {
+        if value.is_empty() || value.len() > 255 {
+            return Err(format!("Invalid skill name: '{}'", value));
+        }
+        if value == "." || value == ".." || value.contains(['/', '\\', '\0']) {
+            return Err(format!("Invalid skill name: '{}'", value));
+        }
+        Ok(Self(value.to_string()))
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for SkillName {
+    fn as_ref(&self) -> &Path {
+        Path::new(&self.0)
+    }
+}
+
+impl std::fmt::Display for SkillName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Parse a relative path coming from the frontend (e.g. `skills_path`).
+/// `"."` and `""` map to an empty path; `..`, absolute paths, drive prefixes and
+/// backslashes (a separator on Windows only) are rejected.
+pub(crate) fn parse_relative_path(value: &str) -> Result<PathBuf, String> {
+    if value.contains(['\0', '\\']) {
+        return Err(format!("Invalid path: '{}'", value));
+    }
+
+    let mut path = PathBuf::new();
+    for component in Path::new(value).components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            _ => return Err(format!("Invalid path: '{}'", value)),
+        }
+    }
+    Ok(path)
+}
+
+/// Canonicalize `path` and make sure it resolves inside `root`.
+pub(crate) fn ensure_within(root: &Path, path: &Path) -> Result<PathBuf, String> {
+    let root = root
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {:?}: {}", root, e))?;
+    let resolved = path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {:?}: {}", path, e))?;
+
+    if resolved.starts_with(&root) {
+        Ok(resolved)
+    } else {
+        Err(format!("Path {:?} escapes {:?}", path, root))
+    }
+}
+
+/// Location of a fetched repo in the cache, verified to stay under the repos root.
+pub(crate) fn resolve_repo_path(owner: &str, repo: &str) -> Result<PathBuf, String> {
+    let owner = SkillName::parse(owner)?;
+    let repo = SkillName::parse(repo)?;
+    Ok(get_repos_path().join(owner).join(repo))
+}
+
+/// Checkout of a repo at a pinned ref, kept apart from the regular repo cache.
+pub(crate) fn resolve_pinned_repo_path(owner: &str, repo: &str, git_ref: &str) -> Result<PathBuf, String> {
+    let owner = SkillName::parse(owner)?;
+    let repo = SkillName::parse(repo)?;
+    Ok(get_data_path().join("pinned").join(owner).join(repo).join(hash_key(git_ref)))
+}
+
+/// Source folder of a skill inside the repo cache. The folder must exist.
+pub(crate) fn resolve_skill_source(
+    owner: &str,
+    repo: &str,
+    skills_path: &str,
+    skill_path: &str,
+) -> Result<PathBuf, String> {
+    let repo_path = resolve_repo_path(owner, repo)?;
+    let skill_path = parse_relative_path(skill_path)?;
+    let source = repo_path.join(parse_relative_path(skills_path)?).join(&skill_path);
+
+    if !source.exists() {
+        return Err(format!("Source path does not exist: {:?}", source));
+    }
+    ensure_within(&repo_path, &source)
+}
+
+/// Install location of a skill under `root`. The returned path may not exist yet,
+/// but its parent is always `root` itself.
+pub(crate) fn resolve_install_path(root: &Path, name: &SkillName) -> Result<PathBuf, String> {
+    fs::create_dir_all(root).map_err(|e| format!("Failed to create directory: {}", e))?;
+    let root = root
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {:?}: {}", root, e))?;
+    Ok(root.join(name))
+}
+
+/// Fresh, empty directory for a test, unique per process and call.
+#[cfg(test)]
+pub(crate) fn test_dir(label: &str) -> PathBuf {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let dir = std::env::temp_dir().join(format!(
+        "skill-studio-test-{}-{}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::SeqCst),
+        label
+    ));
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skill_name_rejects_traversal() {
+        for name in ["", ".", "..", "../etc", "a/b", "a\\b", "..\\..", "/etc", "a\0b"] {
+            assert!(SkillName::parse(name).is_err(), "accepted {:?}", name);
+        }
+        assert_eq!(SkillName::parse("my-skill").unwrap().as_str(), "my-skill");
+        assert!(SkillName::parse("..hidden").is_ok());
+    }
+
+    #[test]
+    fn relative_path_rejects_traversal() {
+        for path in ["..", "a/../..", "skills/../../etc", "/etc/passwd", "a\\..\\..", "..\\etc", "a\0b"] {
+            assert!(parse_relative_path(path).is_err(), "accepted {:?}", path);
+        }
+        assert_eq!(parse_relative_path(".").unwrap(), PathBuf::new());
+        assert_eq!(parse_relative_path("").unwrap(), PathBuf::new());
+        assert_eq!(parse_relative_path("./src/skills/").unwrap(), PathBuf::from("src/skills"));
+    }
+
+    #[test]
+    fn ensure_within_rejects_escapes() {
+        let dir = test_dir("ensure-within");
+        let root = dir.join("root");
+        fs::create_dir_all(root.join("inside")).unwrap();
+        fs::create_dir_all(dir.join("outside")).unwrap();
+
+        assert!(ensure_within(&root, &root.join("inside")).is_ok());
+        assert!(ensure_within(&root, &root.join("inside/../../outside")).is_err());
+        assert!(ensure_within(&root, &dir.join("outside")).is_err());
+        assert!(ensure_within(&root, &root.join("missing")).is_err());
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(dir.join("outside"), root.join("link")).unwrap();
+            assert!(ensure_within(&root, &root.join("link")).is_err());
+        }
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn install_path_stays_under_root() {
+        let dir = test_dir("install-path");
+        let name = SkillName::parse("my-skill").unwrap();
+        let path = resolve_install_path(&dir.join("skills"), &name).unwrap();
+        assert_eq!(path, dir.join("skills").canonicalize().unwrap().join("my-skill"));
+        fs::remove_dir_all(&dir).ok();
+    }
+}
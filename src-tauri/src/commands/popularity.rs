@@ -0,0 +1,305 @@
+use super::catalog::fetch_catalog_text;
+use super::paths::get_data_path;
+use super::settings::load_settings;
+use super::skills::{chrono_now, collect_all_skills};
+use crate::models::{PopularityEntry, PopularityFeed, PopularityInfo, Skill};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// The last ingested feed, with the counts of the snapshot before it so trending
+/// skills can be found even when the feed has no recent install counts.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedPopularity {
+    source: String,
+    fetched_at: String,
+    feed: PopularityFeed,
+    #[serde(default)]
+    previous: HashMap<String, u64>, // entry key -> installs
+    #[serde(default)]
+    previous_fetched_at: Option<String>,
+}
+
+fn get_popularity_path() -> PathBuf {
+    get_data_path().join("popularity.json")
+}
+
+// Cached feed, ignored once settings point to a different source
+fn load_cached_popularity() -> Option<CachedPopularity> {
+    let source = load_settings().popularity_source?;
+    fs::read_to_string(get_popularity_path())
+        .ok()
+        .and_then(|c| serde_json::from_str::<CachedPopularity>(&c).ok())
+        .filter(|cached| cached.source == source)
+}
+
+// "owner/repo/skill" for entries given by id or by source and name
+fn entry_key(entry: &PopularityEntry) -> Option<String> {
+    match (&entry.id, &entry.source, &entry.name) {
+        (Some(id), _, _) => Some(id.to_lowercase()),
+        (None, Some(source), Some(name)) => Some(format!("{}/{}", source, name).to_lowercase()),
+        _ => None,
+    }
+}
+
+fn parse_feed(content: &str) -> Result<PopularityFeed, String> {
+    // A bare list of entries is accepted as well as a feed object
+    serde_json::from_str::<PopularityFeed>(content)
+        .or_else(|_| {
+            serde_json::from_str::<Vec<PopularityEntry>>(content).map(|skills| PopularityFeed {
+                updated_at: None,
+                skills,
+            })
+        })
+        .map_err(|e| format!("Invalid popularity feed: {}", e))
+}
+
+fn counts(feed: &PopularityFeed) -> HashMap<String, u64> {
+    feed.skills
+        .iter()
+        .filter_map(|entry| entry_key(entry).map(|key| (key, entry.installs)))
+        .collect()
+}
+
+fn info(cached: &CachedPopularity) -> PopularityInfo {
+    PopularityInfo {
+        source: cached.source.clone(),
+        fetched_at: cached.fetched_at.clone(),
+        updated_at: cached.feed.updated_at.clone(),
+        skill_count: cached.feed.skills.len(),
+        previous_fetched_at: cached.previous_fetched_at.clone(),
+    }
+}
+
+// The new snapshot to cache. The old feed becomes the previous snapshot only when its
+// counts differ, so refreshing an unchanged feed does not wipe out the trend.
+fn rotate(
+    old: Option<CachedPopularity>,
+    source: String,
+    feed: PopularityFeed,
+    fetched_at: String,
+) -> CachedPopularity {
+    let (previous, previous_fetched_at) = match old {
+        Some(old) if counts(&old.feed) != counts(&feed) => (counts(&old.feed), Some(old.fetched_at)),
+        Some(old) => (old.previous, old.previous_fetched_at),
+        None => (HashMap::new(), None),
+    };
+    CachedPopularity {
+        source,
+        fetched_at,
+        feed,
+        previous,
+        previous_fetched_at,
+    }
+}
+
+/// Download (or read) the configured feed and cache it, keeping the previous
+/// snapshot's counts when they changed. Returns None when no source is configured.
+pub(crate) fn ingest_popularity() -> Result<Option<PopularityInfo>, String> {
+    let Some(source) = load_settings().popularity_source.filter(|s| !s.trim().is_empty()) else {
+        return Ok(None);
+    };
+    let content = if source.starts_with("https://") || source.starts_with("http://") {
+        fetch_catalog_text(&source)?
+    } else {
+        fs::read_to_string(&source).map_err(|e| format!("Failed to read {}: {}", source, e))?
+    };
+    let cached = rotate(load_cached_popularity(), source, parse_feed(&content)?, chrono_now());
+
+    fs::create_dir_all(get_data_path()).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(&cached).map_err(|e| e.to_string())?;
+    fs::write(get_popularity_path(), content).map_err(|e| format!("Failed to cache popularity feed: {}", e))?;
+    Ok(Some(info(&cached)))
+}
+
+/// Install counts of the cached feed, looked up by skill or repo.
+pub(crate) struct Popularity {
+    counts: HashMap<String, (u64, Option<u64>)>, // entry key -> (installs, recent installs)
+}
+
+impl Popularity {
+    pub(crate) fn load() -> Option<Self> {
+        load_cached_popularity().map(|cached| Self::from_cached(&cached))
+    }
+
+    fn from_cached(cached: &CachedPopularity) -> Self {
+        let has_previous = cached.previous_fetched_at.is_some();
+        let counts = cached
+            .feed
+            .skills
+            .iter()
+            .filter_map(|entry| {
+                let key = entry_key(entry)?;
+                // Without recent counts in the feed, growth since the previous snapshot is used;
+                // entries new since then gained all their installs in between
+                let recent = entry.recent_installs.or_else(|| match cached.previous.get(&key) {
+                    Some(previous) => Some(entry.installs.saturating_sub(*previous)),
+                    None => has_previous.then_some(entry.installs),
+                });
+                Some((key, (entry.installs, recent)))
+            })
+            .collect();
+        Self { counts }
+    }
+
+    fn for_skill(&self, skill: &Skill) -> Option<(u64, Option<u64>)> {
+        let by_name = format!("{}/{}/{}", skill.owner, skill.repo, skill.name).to_lowercase();
+        self.counts
+            .get(&skill.id.to_lowercase())
+            .or_else(|| self.counts.get(&by_name))
+            .copied()
+    }
+
+    /// Totals over every skill of the repo in the feed.
+    pub(crate) fn for_repo(&self, owner: &str, repo: &str) -> Option<(u64, Option<u64>)> {
+        let prefix = format!("{}/{}/", owner, repo).to_lowercase();
+        self.counts
+            .iter()
+            .filter(|(key, _)| key.starts_with(&prefix))
+            .map(|(_, counts)| *counts)
+            .reduce(|(installs, recent), (more, more_recent)| {
+                let recent = match (recent, more_recent) {
+                    (None, None) => None,
+                    (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+                };
+                (installs + more, recent)
+            })
+    }
+
+    pub(crate) fn apply(&self, skills: &mut [Skill]) {
+        for skill in skills {
+            if let Some((installs, recent)) = self.for_skill(skill) {
+                skill.installs = Some(installs);
+                skill.recent_installs = recent;
+            }
+        }
+    }
+}
+
+/// Order skills for a "popular" or "trending" query, leaving out skills without counts.
+pub(crate) fn rank_skills(mut skills: Vec<Skill>, sort: &str, limit: Option<usize>) -> Result<Vec<Skill>, String> {
+    let count: fn(&Skill) -> Option<u64> = match sort {
+        "popular" => |s: &Skill| s.installs,
+        "trending" => |s: &Skill| s.recent_installs,
+        other => return Err(format!("Unknown sort '{}', expected popular or trending", other)),
+    };
+    skills.retain(|s| count(s).is_some_and(|c| c > 0));
+    skills.sort_by(|a, b| count(b).cmp(&count(a)).then_with(|| a.name.cmp(&b.name)));
+    skills.truncate(limit.unwrap_or(usize::MAX));
+    Ok(skills)
+}
+
+/// Download (or read) the configured popularity feed and cache it for ranking.
+#[tauri::command]
+pub async fn refresh_popularity() -> Result<Option<PopularityInfo>, String> {
+    tauri::async_runtime::spawn_blocking(ingest_popularity)
+        .await
+        .map_err(|e| format!("Failed to refresh popularity: {}", e))?
+}
+
+/// The most installed skills ("popular") or those gaining installs fastest ("trending").
+#[tauri::command]
+pub async fn get_ranked_skills(sort: String, limit: Option<usize>) -> Result<Vec<Skill>, String> {
+    tauri::async_runtime::spawn_blocking(move || rank_skills(collect_all_skills(), &sort, limit))
+        .await
+        .map_err(|e| format!("Failed to rank skills: {}", e))?
+}
+
+#[tauri::command]
+pub async fn get_popularity_info() -> Result<Option<PopularityInfo>, String> {
+    Ok(load_cached_popularity().as_ref().map(info))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(entries: &[(&str, u64)]) -> PopularityFeed {
+        PopularityFeed {
+            updated_at: None,
+            skills: entries
+                .iter()
+                .map(|(id, installs)| PopularityEntry {
+                    id: Some(id.to_string()),
+                    source: None,
+                    name: None,
+                    installs: *installs,
+                    recent_installs: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn ingest(old: Option<CachedPopularity>, entries: &[(&str, u64)], at: &str) -> CachedPopularity {
+        rotate(old, "feed.json".to_string(), feed(entries), at.to_string())
+    }
+
+    #[test]
+    fn rotates_snapshots_only_when_counts_change() {
+        let first = ingest(None, &[("a/b/one", 10)], "100");
+        assert!(first.previous.is_empty());
+        assert_eq!(first.previous_fetched_at, None);
+
+        let second = ingest(Some(first), &[("a/b/one", 15), ("a/b/two", 4)], "200");
+        assert_eq!(second.previous, HashMap::from([("a/b/one".to_string(), 10)]));
+        assert_eq!(second.previous_fetched_at.as_deref(), Some("100"));
+
+        // Same counts again: the snapshot before stays the baseline
+        let third = ingest(Some(second), &[("a/b/two", 4), ("a/b/one", 15)], "300");
+        assert_eq!(third.fetched_at, "300");
+        assert_eq!(third.previous, HashMap::from([("a/b/one".to_string(), 10)]));
+        assert_eq!(third.previous_fetched_at.as_deref(), Some("100"));
+    }
+
+    #[test]
+    fn recent_installs_come_from_the_feed_or_the_previous_snapshot() {
+        let first = ingest(None, &[("a/b/one", 10)], "100");
+        assert_eq!(Popularity::from_cached(&first).counts["a/b/one"], (10, None));
+
+        let mut second = ingest(Some(first), &[("A/B/one", 15), ("a/b/new", 4), ("a/b/given", 9)], "200");
+        second.feed.skills[2].recent_installs = Some(2);
+        let popularity = Popularity::from_cached(&second);
+        assert_eq!(popularity.counts["a/b/one"], (15, Some(5)));
+        // Entries missing from the previous snapshot trend with all their installs
+        assert_eq!(popularity.counts["a/b/new"], (4, Some(4)));
+        assert_eq!(popularity.counts["a/b/given"], (9, Some(2)));
+    }
+
+    fn skill(name: &str, installs: Option<u64>, recent_installs: Option<u64>) -> Skill {
+        Skill {
+            id: format!("a/b/{}", name),
+            name: name.to_string(),
+            description: String::new(),
+            owner: "a".to_string(),
+            repo: "b".to_string(),
+            skills_path: "skills".to_string(),
+            path: name.to_string(),
+            content: None,
+            is_installed: false,
+            is_fetched: false,
+            dependencies: Vec::new(),
+            is_disabled: false,
+            installs,
+            recent_installs,
+        }
+    }
+
+    #[test]
+    fn ranks_by_installs_or_recent_installs() {
+        let skills = vec![
+            skill("steady", Some(100), Some(1)),
+            skill("rising", Some(20), Some(15)),
+            skill("tied", Some(20), Some(0)),
+            skill("unknown", None, None),
+        ];
+        let names = |ranked: Vec<Skill>| ranked.into_iter().map(|s| s.name).collect::<Vec<_>>();
+
+        let popular = rank_skills(skills.clone(), "popular", None).unwrap();
+        // Equal counts are ordered by name; skills without counts are left out
+        assert_eq!(names(popular), ["steady", "rising", "tied"]);
+        let trending = rank_skills(skills.clone(), "trending", Some(1)).unwrap();
+        assert_eq!(names(trending), ["rising"]);
+        assert!(rank_skills(skills, "newest", None).is_err());
+    }
+}
@@ -0,0 +1,334 @@
+use super::catalog::{catalog_skills_path, load_merged_catalog};
+use super::git::{github_url, run_git, validate_git_ref};
+use super::install::copy_skill_dir;
+use super::paths::{ensure_within, get_local_skills_path, parse_relative_path, unique_staging_path, SkillName};
+use super::skills::{scan_repo_dir, validate_skill_dir};
+use crate::models::{PublishResult, PublishSkillRequest};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const DEFAULT_SKILLS_PATH: &str = "skills";
+const API_TIMEOUT: Duration = Duration::from_secs(15);
+
+fn resolve_source(source: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(source);
+    if path.is_dir() {
+        return Ok(path);
+    }
+    SkillName::parse(source)
+        .ok()
+        .map(|name| get_local_skills_path().join(name))
+        .filter(|local| local.is_dir())
+        .ok_or_else(|| format!("Skill folder or local skill '{}' not found", source))
+}
+
+// (owner, repo) when the remote is on GitHub, given as "owner/repo" or a GitHub URL
+fn github_repo(remote: &str) -> Option<(String, String)> {
+    if Path::new(remote).exists() {
+        return None;
+    }
+    let rest = remote
+        .strip_prefix("https://github.com/")
+        .or_else(|| remote.strip_prefix("git@github.com:"))
+        .unwrap_or(remote)
+        .trim_end_matches('/')
+        .trim_end_matches(".git");
+    let (owner, repo) = rest.split_once('/')?;
+    (SkillName::parse(owner).is_ok() && SkillName::parse(repo).is_ok()).then(|| (owner.to_string(), repo.to_string()))
+}
+
+fn remote_url(remote: &str) -> Result<String, String> {
+    if remote.starts_with('-') || remote.trim().is_empty() {
+        return Err(format!("Invalid remote: '{}'", remote));
+    }
+    let is_shorthand = !remote.contains(':') && github_repo(remote).is_some();
+    Ok(match github_repo(remote) {
+        Some((owner, repo)) if is_shorthand => github_url(&owner, &repo),
+        _ => remote.to_string(),
+    })
+}
+
+// Explicit path, then the catalog's, then where the repo already keeps its skills
+fn skills_path_for(request: &PublishSkillRequest, checkout: &Path) -> String {
+    if let Some(path) = request.skills_path.as_deref().filter(|p| !p.trim().is_empty()) {
+        return path.trim_matches('/').to_string();
+    }
+    let catalog = load_merged_catalog().0;
+    if let Some(path) = github_repo(&request.remote).and_then(|(owner, repo)| catalog_skills_path(&catalog, &owner, &repo)) {
+        return path.trim_matches('/').to_string();
+    }
+    scan_repo_dir(checkout, "", "", None, &[])
+        .into_iter()
+        .next()
+        .map(|skill| skill.skills_path)
+        .unwrap_or_else(|| DEFAULT_SKILLS_PATH.to_string())
+}
+
+fn open_pull_request(
+    owner: &str,
+    repo: &str,
+    branch: &str,
+    base: &str,
+    title: &str,
+    body: &str,
+) -> Result<String, String> {
+    let token = std::env::var("GITHUB_TOKEN")
+        .or_else(|_| std::env::var("GH_TOKEN"))
+        .map_err(|_| "Set GITHUB_TOKEN or GH_TOKEN to open pull requests".to_string())?;
+    let payload = serde_json::json!({ "title": title, "head": branch, "base": base, "body": body });
+
+    let response = ureq::post(&format!("https://api.github.com/repos/{}/{}/pulls", owner, repo))
+        .timeout(API_TIMEOUT)
+        .set("Accept", "application/vnd.github+json")
+        .set("Authorization", &format!("Bearer {}", token))
+        .set("User-Agent", "skill-studio")
+        .set("Content-Type", "application/json")
+        .send_string(&payload.to_string())
+        .map_err(|e| match e {
+            ureq::Error::Status(code, response) => format!(
+                "GitHub API returned HTTP {}: {}",
+                code,
+                response.into_string().unwrap_or_default()
+            ),
+            e => format!("Failed to reach GitHub: {}", e),
+        })?;
+    let body: serde_json::Value = serde_json::from_str(&response.into_string().map_err(|e| e.to_string())?)
+        .map_err(|e| format!("Unexpected GitHub response: {}", e))?;
+    body["html_url"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| "GitHub response has no pull request URL".to_string())
+}
+
+// Clones the target repo into `staging` and commits there; `git_config` holds extra
+// `-c name=value` arguments for the commit
+fn publish_in(request: &PublishSkillRequest, staging: &Path, git_config: &[&str]) -> Result<PublishResult, String> {
+    let source = resolve_source(&request.source)?;
+    let frontmatter = validate_skill_dir(&source)?;
+    let name = SkillName::parse(&frontmatter.name)?;
+    if let Some(base) = &request.base_branch {
+        validate_git_ref(base)?;
+    }
+
+    let url = remote_url(&request.remote)?;
+    let staging_str = staging.to_str().ok_or("Invalid staging path")?;
+    let mut clone_args = vec!["clone", "--quiet", "--depth", "1"];
+    if let Some(base) = &request.base_branch {
+        clone_args.extend(["--branch", base.as_str()]);
+    }
+    clone_args.extend(["--", url.as_str(), staging_str]);
+    run_git(&clone_args, None)?;
+
+    // A repo without commits has no base branch; the skill becomes its first commit
+    let has_commits = run_git(&["rev-parse", "--verify", "--quiet", "HEAD"], Some(staging)).is_ok();
+    let base_branch = if has_commits {
+        Some(run_git(&["symbolic-ref", "--short", "HEAD"], Some(staging))?)
+    } else {
+        None
+    };
+
+    let skills_path = skills_path_for(request, staging);
+    let repo_path = parse_relative_path(&skills_path)?.join(&name);
+    let dest = staging.join(&repo_path);
+    let is_update = dest.exists();
+
+    let branch = match &request.branch {
+        Some(branch) => branch.clone(),
+        None if is_update => format!("update-skill-{}", name),
+        None => format!("add-skill-{}", name),
+    };
+    validate_git_ref(&branch)?;
+    if !run_git(&["ls-remote", "--heads", "origin", &format!("refs/heads/{}", branch)], Some(staging))?.is_empty() {
+        return Err(format!("Branch '{}' already exists on the remote", branch));
+    }
+    run_git(&["checkout", "--quiet", "-b", &branch], Some(staging))?;
+
+    // The target repo is not trusted: writing through a symlink could land outside the checkout
+    let mut checked = PathBuf::new();
+    for component in repo_path.components() {
+        checked.push(component);
+        if staging.join(&checked).is_symlink() {
+            return Err(format!("{} is a symlink in the target repo", checked.display()));
+        }
+    }
+
+    // Replace the whole folder so files removed locally are removed upstream too
+    if is_update {
+        ensure_within(staging, &dest)?;
+        fs::remove_dir_all(&dest).map_err(|e| format!("Failed to replace skill: {}", e))?;
+    }
+    let parent = dest.parent().ok_or("Invalid skill path")?;
+    fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", skills_path, e))?;
+    let written = copy_skill_dir(&source, &dest)?;
+
+    let repo_path_str = repo_path.to_string_lossy().replace('\\', "/");
+    run_git(&["add", "--all", "--", &repo_path_str], Some(staging))?;
+    if run_git(&["status", "--porcelain", "--", &repo_path_str], Some(staging))?.is_empty() {
+        return Err(format!("'{}' is already published with the same content", name));
+    }
+
+    let title = format!("{} {} skill", if is_update { "Update" } else { "Add" }, name);
+    let message = request
+        .message
+        .clone()
+        .unwrap_or_else(|| format!("{}\n\n{}", title, frontmatter.description));
+    let mut commit_args = git_config.to_vec();
+    commit_args.extend(["commit", "--quiet", "-m", &message]);
+    run_git(&commit_args, Some(staging)).map_err(|e| {
+        if e.contains("Author identity unknown") {
+            "Set git user.name and user.email to publish skills".to_string()
+        } else {
+            e
+        }
+    })?;
+    let commit = run_git(&["rev-parse", "HEAD"], Some(staging))?;
+    run_git(&["push", "--quiet", "origin", &format!("{}:refs/heads/{}", branch, branch)], Some(staging))?;
+
+    // The push already happened, so a failed pull request is reported rather than returned as an error
+    let (pull_request_url, pull_request_error) = if request.open_pull_request {
+        let pull_request = match (github_repo(&request.remote), &base_branch) {
+            (None, _) => Err("Pull requests can only be opened for GitHub repos".to_string()),
+            (_, None) => Err("The repo has no branch to open a pull request against".to_string()),
+            (Some((owner, repo)), Some(base)) => {
+                open_pull_request(&owner, &repo, &branch, base, &title, &frontmatter.description)
+            }
+        };
+        match pull_request {
+            Ok(url) => (Some(url), None),
+            Err(e) => (None, Some(e)),
+        }
+    } else {
+        (None, None)
+    };
+
+    Ok(PublishResult {
+        name: name.to_string(),
+        remote: request.remote.clone(),
+        branch,
+        base_branch,
+        path: repo_path_str,
+        commit,
+        is_update,
+        files: written
+            .iter()
+            .filter_map(|path| path.strip_prefix(staging).ok())
+            .map(|path| path.to_string_lossy().replace('\\', "/"))
+            .collect(),
+        pull_request_url,
+        pull_request_error,
+    })
+}
+
+pub(crate) fn publish(request: &PublishSkillRequest) -> Result<PublishResult, String> {
+    // Each publish gets its own clone, so concurrent publishes to one remote don't collide
+    let staging = unique_staging_path("publish-staging");
+    if let Some(parent) = staging.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create dir: {}", e))?;
+    }
+    let result = publish_in(request, &staging, &[]);
+    fs::remove_dir_all(&staging).ok();
+    result
+}
+
+/// Validate a local skill, commit it into the target repo's skills folder on a new
+/// branch and push it, optionally opening a pull request on GitHub.
+#[tauri::command]
+pub async fn publish_skill(request: PublishSkillRequest) -> Result<PublishResult, String> {
+    tauri::async_runtime::spawn_blocking(move || publish(&request))
+        .await
+        .map_err(|e| format!("Publish failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::git::{test_push, test_remote, TEST_IDENTITY};
+    use crate::commands::paths::test_dir;
+
+    fn write_skill(dir: &Path, description: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("SKILL.md"), format!("---\nname: demo\ndescription: {}\n---\n", description)).unwrap();
+    }
+
+    // A bare remote with one commit on its default branch, holding `demo` at `existing` if given
+    fn bare_remote(dir: &Path, existing: Option<&str>) -> PathBuf {
+        let (remote, work) = test_remote(dir);
+        fs::write(work.join("README.md"), "# Skills\n").unwrap();
+        if let Some(existing) = existing {
+            write_skill(&work.join(existing).join("demo"), "old");
+        }
+        test_push(&work, "Initial commit");
+        remote
+    }
+
+    fn request(source: &Path, remote: &Path) -> PublishSkillRequest {
+        PublishSkillRequest {
+            source: source.to_string_lossy().to_string(),
+            remote: remote.to_string_lossy().to_string(),
+            skills_path: None,
+            branch: None,
+            base_branch: None,
+            message: None,
+            open_pull_request: false,
+        }
+    }
+
+    fn remote_file(remote: &Path, branch: &str, path: &str) -> String {
+        run_git(&["show", &format!("{}:{}", branch, path)], Some(remote)).unwrap()
+    }
+
+    #[test]
+    fn publishes_new_skill_on_a_branch() {
+        let dir = test_dir("publish-add");
+        let remote = bare_remote(&dir, None);
+        let source = dir.join("demo");
+        write_skill(&source, "new");
+
+        let result = publish_in(&request(&source, &remote), &dir.join("staging"), &TEST_IDENTITY).unwrap();
+        assert_eq!(result.branch, "add-skill-demo");
+        assert_eq!(result.path, "skills/demo");
+        assert!(!result.is_update);
+        assert_eq!(result.files, vec!["skills/demo/SKILL.md"]);
+
+        let pushed = run_git(&["rev-parse", "refs/heads/add-skill-demo"], Some(&remote)).unwrap();
+        assert_eq!(pushed, result.commit);
+        let subject = run_git(&["log", "-1", "--format=%s", "add-skill-demo"], Some(&remote)).unwrap();
+        assert_eq!(subject, "Add demo skill");
+        assert!(remote_file(&remote, "add-skill-demo", "skills/demo/SKILL.md").contains("description: new"));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn updates_skill_already_in_the_repo() {
+        let dir = test_dir("publish-update");
+        let remote = bare_remote(&dir, Some("src/skills"));
+        let source = dir.join("demo");
+        write_skill(&source, "new");
+
+        let result = publish_in(&request(&source, &remote), &dir.join("staging"), &TEST_IDENTITY).unwrap();
+        assert_eq!(result.branch, "update-skill-demo");
+        assert_eq!(result.path, "src/skills/demo");
+        assert!(result.is_update);
+
+        let pushed = run_git(&["rev-parse", "refs/heads/update-skill-demo"], Some(&remote)).unwrap();
+        assert_eq!(pushed, result.commit);
+        let content = remote_file(&remote, "update-skill-demo", "src/skills/demo/SKILL.md");
+        assert!(content.contains("description: new"));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn refuses_existing_branch() {
+        let dir = test_dir("publish-branch-exists");
+        let remote = bare_remote(&dir, None);
+        let source = dir.join("demo");
+        write_skill(&source, "new");
+
+        let request = request(&source, &remote);
+        publish_in(&request, &dir.join("staging"), &TEST_IDENTITY).unwrap();
+        let error = publish_in(&request, &dir.join("staging-again"), &TEST_IDENTITY).unwrap_err();
+        assert_eq!(error, "Branch 'add-skill-demo' already exists on the remote");
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -9,6 +9,14 @@ fn get_settings_path() -> PathBuf {
         .join("settings.json")
 }
 
+// Settings for backend code paths, falling back to defaults when unreadable
+pub(crate) fn load_settings() -> Settings {
+    fs::read_to_string(get_settings_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
 #[tauri::command]
 pub async fn get_settings() -> Result<Settings, String> {
     let settings_path = get_settings_path();
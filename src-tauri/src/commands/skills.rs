@@ -1,13 +1,46 @@
-use crate::models::{Catalog, FetchedRepos, Skill};
+use super::git::{github_url, shallow_clone};
+use super::agents::{selected_targets, user_targets};
+use super::catalog::{catalog_skills_path, load_active_catalog, load_merged_catalog, load_skill_index, MergedRepo};
+use super::paths::{get_data_path, parse_relative_path, resolve_repo_path, SkillName};
+use super::popularity::Popularity;
+use crate::models::{Catalog, FetchedRepos, InstalledSkill, Skill, SkillIndex, TargetStatus};
 use regex::Regex;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
-use tauri::Manager;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, OnceLock};
 
-fn get_library_path(app: &tauri::AppHandle) -> PathBuf {
+// Bundled resources folder, recorded by the desktop app at startup
+static RESOURCE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+// Held across each load-modify-save of fetched-repos.json, which parallel fetches share
+static FETCHED_REPOS_LOCK: Mutex<()> = Mutex::new(());
+
+// One lock per "owner/repo", so a cache folder is never replaced by two fetches at once
+static REPO_LOCKS: Mutex<BTreeMap<String, Arc<Mutex<()>>>> = Mutex::new(BTreeMap::new());
+
+pub(crate) fn set_resource_dir(resource_dir: PathBuf) {
+    let _ = RESOURCE_DIR.set(resource_dir);
+}
+
+/// Bundled resources folder found relative to the executable the way Tauri lays it out,
+/// for the CLI which runs without a window and so without Tauri's path resolver.
+pub(crate) fn find_resource_dir() -> Option<PathBuf> {
+    let exe_path = std::env::current_exe().ok()?;
+    let exe_dir = exe_path.parent()?;
+    [
+        exe_dir.join("../Resources"),         // macOS app bundle
+        exe_dir.join("../lib/skill-studio"),  // Linux packages
+        exe_dir.join("../lib/Skill Studio"),
+    ]
+    .into_iter()
+    .find(|dir| dir.join("library").join("catalog.json").exists())
+}
+
+pub(crate) fn get_library_path() -> PathBuf {
     // Try resource_dir first (for bundled apps)
-    if let Ok(resource_dir) = app.path().resource_dir() {
+    if let Some(resource_dir) = RESOURCE_DIR.get() {
         let lib_path = resource_dir.join("library");
         if lib_path.join("catalog.json").exists() {
             return lib_path;
@@ -27,12 +60,6 @@ fn get_library_path(app: &tauri::AppHandle) -> PathBuf {
     PathBuf::from(".").join("library")
 }
 
-fn get_data_path() -> PathBuf {
-    dirs::data_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("skill-studio")
-}
-
 fn get_fetched_repos_path() -> PathBuf {
     dirs::config_dir()
         .unwrap_or_else(|| PathBuf::from("."))
@@ -40,14 +67,7 @@ fn get_fetched_repos_path() -> PathBuf {
         .join("fetched-repos.json")
 }
 
-fn get_installed_skills_path() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join(".claude")
-        .join("skills")
-}
-
-fn load_fetched_repos() -> FetchedRepos {
+pub(crate) fn load_fetched_repos() -> FetchedRepos {
     let path = get_fetched_repos_path();
     if path.exists() {
         fs::read_to_string(&path)
@@ -68,40 +88,167 @@ fn save_fetched_repos(repos: &FetchedRepos) -> Result<(), String> {
     fs::write(&path, content).map_err(|e| e.to_string())
 }
 
-fn parse_frontmatter(content: &str) -> (String, String) {
-    let re = Regex::new(r"(?s)^---\n(.*?)\n---").unwrap();
+fn update_fetched_repos(update: impl FnOnce(&mut FetchedRepos)) -> Result<(), String> {
+    let _guard = FETCHED_REPOS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut fetched = load_fetched_repos();
+    update(&mut fetched);
+    save_fetched_repos(&fetched)
+}
+
+fn repo_lock(repo_key: &str) -> Arc<Mutex<()>> {
+    let mut locks = REPO_LOCKS.lock().unwrap_or_else(|e| e.into_inner());
+    locks.entry(repo_key.to_string()).or_default().clone()
+}
+
+pub(crate) struct Frontmatter {
+    pub name: String,
+    pub description: String,
+    pub dependencies: Vec<String>,
+}
+
+/// Name given to skills whose frontmatter has none.
+pub(crate) const UNKNOWN_NAME: &str = "Unknown";
+
+pub(crate) fn parse_frontmatter(content: &str) -> Frontmatter {
+    let re = Regex::new(r"(?s)^---\r?\n(.*?)\r?\n---").unwrap();
 
     if let Some(caps) = re.captures(content) {
         let frontmatter = &caps[1];
 
-        let name = Regex::new(r"name:\s*(.+)")
+        let name = Regex::new(r"(?m)^name:\s*(.+)")
             .unwrap()
             .captures(frontmatter)
-            .map(|c| c[1].trim().to_string())
-            .unwrap_or_else(|| "Unknown".to_string());
+            .map(|c| unquote(c[1].trim()))
+            .unwrap_or_else(|| UNKNOWN_NAME.to_string());
 
-        let description = Regex::new(r"description:\s*(.+)")
+        let description = Regex::new(r"(?m)^description:\s*(.+)")
             .unwrap()
             .captures(frontmatter)
-            .map(|c| c[1].trim().to_string())
+            .map(|c| unquote(c[1].trim()))
             .unwrap_or_default();
 
-        return (name, description);
+        let dependencies = parse_frontmatter_list(frontmatter, "dependencies");
+
+        return Frontmatter { name, description, dependencies };
     }
 
-    ("Unknown".to_string(), String::new())
+    Frontmatter {
+        name: UNKNOWN_NAME.to_string(),
+        description: String::new(),
+        dependencies: Vec::new(),
+    }
 }
 
-#[tauri::command]
-pub async fn get_catalog(app: tauri::AppHandle) -> Result<Catalog, String> {
-    let library_path = get_library_path(&app);
-    let catalog_path = library_path.join("catalog.json");
+/// Replace the frontmatter `name` of a SKILL.md, None if it has none.
+pub(crate) fn set_frontmatter_name(content: &str, name: &str) -> Option<String> {
+    let block = Regex::new(r"(?s)^---\r?\n(.*?)\r?\n---").unwrap().captures(content)?.get(1)?;
+    let line = Regex::new(r"(?m)^name:[^\r\n]*").unwrap().find(block.as_str())?;
+
+    let start = block.start() + line.start();
+    let end = block.start() + line.end();
+    Some(format!("{}name: {}{}", &content[..start], name, &content[end..]))
+}
+
+// Reads either `key: [a, b]` or a block list of `- a` lines following `key:`
+fn parse_frontmatter_list(frontmatter: &str, key: &str) -> Vec<String> {
+    let mut lines = frontmatter.lines();
+    let prefix = format!("{}:", key);
+
+    while let Some(line) = lines.next() {
+        let Some(rest) = line.strip_prefix(&prefix) else {
+            continue;
+        };
+        let rest = rest.trim();
+
+        if let Some(inline) = rest.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+            return inline
+                .split(',')
+                .map(|item| unquote(item.trim()))
+                .filter(|item| !item.is_empty())
+                .collect();
+        }
+        if !rest.is_empty() {
+            return vec![unquote(rest)];
+        }
+
+        return lines
+            .map(str::trim)
+            .take_while(|l| l.starts_with('-'))
+            .map(|l| unquote(l.trim_start_matches('-').trim()))
+            .filter(|item| !item.is_empty())
+            .collect();
+    }
+
+    Vec::new()
+}
+
+/// Check a skill folder the way scanning does: a SKILL.md with frontmatter
+/// naming the skill with a valid folder name.
+pub(crate) fn validate_skill_dir(dir: &Path) -> Result<Frontmatter, String> {
+    let skill_file = find_skill_file(dir).ok_or("Skill folder has no SKILL.md")?;
+    let content = fs::read_to_string(&skill_file).map_err(|e| format!("Failed to read SKILL.md: {}", e))?;
+
+    let frontmatter = parse_frontmatter(&content);
+    if frontmatter.name == UNKNOWN_NAME {
+        return Err("SKILL.md frontmatter has no name".to_string());
+    }
+    SkillName::parse(&frontmatter.name)?;
+    if frontmatter.description.is_empty() {
+        return Err("SKILL.md frontmatter has no description".to_string());
+    }
+    Ok(frontmatter)
+}
+
+// YAML quoting: double quotes with backslash escapes, single quotes with '' for a quote.
+// Anything else just loses stray quotes at either end.
+fn unquote(value: &str) -> String {
+    let quoted = |quote: char| {
+        (value.len() >= 2)
+            .then(|| value.strip_prefix(quote)?.strip_suffix(quote))
+            .flatten()
+    };
+
+    if let Some(inner) = quoted('"') {
+        let mut unescaped = String::with_capacity(inner.len());
+        let mut chars = inner.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                unescaped.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => unescaped.push('\n'),
+                Some('t') => unescaped.push('\t'),
+                Some(escaped) => unescaped.push(escaped),
+                None => unescaped.push('\\'),
+            }
+        }
+        return unescaped;
+    }
+    if let Some(inner) = quoted('\'') {
+        return inner.replace("''", "'");
+    }
+    value.trim_matches(|c| c == '"' || c == '\'').to_string()
+}
 
-    let content = fs::read_to_string(&catalog_path)
-        .map_err(|e| format!("Failed to read catalog: {}", e))?;
+#[tauri::command]
+pub async fn get_catalog() -> Result<Catalog, String> {
+    load_catalog()
+}
 
-    serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse catalog: {}", e))
+// Repos and bundles merged from every enabled catalog source, versioned like the official catalog
+pub(crate) fn load_catalog() -> Result<Catalog, String> {
+    let (version, last_updated) = load_active_catalog()
+        .map(|(catalog, _)| (catalog.version, catalog.last_updated))
+        .unwrap_or_default();
+    let (repos, bundles) = load_merged_catalog();
+
+    Ok(Catalog {
+        version,
+        last_updated,
+        repos: repos.into_iter().map(|r| r.repo).collect(),
+        bundles,
+    })
 }
 
 #[derive(serde::Serialize)]
@@ -112,32 +259,121 @@ pub struct RepoInfo {
     pub is_fetched: bool,
     pub is_custom: bool,
     pub highlight: bool,
+    pub catalogs: Vec<String>, // catalog sources listing this repo, empty for custom repos
+    // Catalog metadata, available before the repo is fetched
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub category: Option<String>,
+    pub maintainer: Option<String>,
+    pub license: Option<String>,
+    pub verified: bool,
+    pub official: bool,
+    pub skills_path: Option<String>,
+    // Totals from the popularity feed over the repo's skills
+    pub installs: Option<u64>,
+    pub recent_installs: Option<u64>,
+}
+
+impl RepoInfo {
+    fn custom(owner: String, repo: String, is_fetched: bool) -> Self {
+        Self {
+            owner,
+            repo,
+            is_fetched,
+            is_custom: true,
+            highlight: false,
+            catalogs: Vec::new(),
+            description: None,
+            tags: Vec::new(),
+            category: None,
+            maintainer: None,
+            license: None,
+            verified: false,
+            official: false,
+            skills_path: None,
+            installs: None,
+            recent_installs: None,
+        }
+    }
+}
+
+/// Narrows `get_all_repos`; every set field must match.
+#[derive(serde::Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoFilter {
+    #[serde(default)]
+    pub query: Option<String>, // matched against owner/repo, description and tags
+    #[serde(default)]
+    pub tags: Vec<String>, // repo must have all of them
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub catalog: Option<String>, // catalog source name, e.g. a team catalog
+    #[serde(default)]
+    pub verified_only: bool,
+    #[serde(default)]
+    pub official_only: bool,
+}
+
+impl RepoFilter {
+    fn matches(&self, info: &RepoInfo) -> bool {
+        let query = self
+            .query
+            .as_deref()
+            .map(|q| q.trim().to_lowercase())
+            .filter(|q| !q.is_empty());
+        let query_matches = query.is_none_or(|query| {
+            format!("{}/{}", info.owner, info.repo).to_lowercase().contains(&query)
+                || info.description.as_deref().is_some_and(|d| d.to_lowercase().contains(&query))
+                || info.tags.iter().any(|t| t.to_lowercase().contains(&query))
+        });
+        let category_matches = self.category.as_deref().is_none_or(|category| {
+            info.category.as_deref().is_some_and(|c| c.eq_ignore_ascii_case(category))
+        });
+
+        query_matches
+            && category_matches
+            && self.tags.iter().all(|tag| info.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+            && self.catalog.as_deref().is_none_or(|c| info.catalogs.iter().any(|ic| ic == c))
+            && (!self.verified_only || info.verified)
+            && (!self.official_only || info.official)
+    }
 }
 
 #[tauri::command]
-pub async fn get_all_repos(app: tauri::AppHandle) -> Result<Vec<RepoInfo>, String> {
-    let library_path = get_library_path(&app);
-    let catalog_path = library_path.join("catalog.json");
+pub async fn get_all_repos(
+    filter: Option<RepoFilter>,
+    sort: Option<String>,
+) -> Result<Vec<RepoInfo>, String> {
     let fetched_repos = load_fetched_repos();
     let custom_repos = load_custom_repos();
 
     let mut repos: Vec<RepoInfo> = Vec::new();
 
     // Add catalog repos
-    if let Ok(catalog_content) = fs::read_to_string(&catalog_path) {
-        if let Ok(catalog) = serde_json::from_str::<Catalog>(&catalog_content) {
-            for catalog_repo in catalog.repos {
-                if let Some((owner, repo)) = catalog_repo.url.split_once('/') {
-                    let repo_key = format!("{}/{}", owner, repo);
-                    repos.push(RepoInfo {
-                        owner: owner.to_string(),
-                        repo: repo.to_string(),
-                        is_fetched: fetched_repos.repos.contains_key(&repo_key),
-                        is_custom: false,
-                        highlight: catalog_repo.highlight,
-                    });
-                }
-            }
+    let (catalog_repos, _) = load_merged_catalog();
+    for merged in catalog_repos {
+        let entry = merged.repo;
+        if let Some((owner, repo)) = entry.url.split_once('/') {
+            let repo_key = format!("{}/{}", owner, repo);
+            repos.push(RepoInfo {
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+                is_fetched: fetched_repos.repos.contains_key(&repo_key),
+                is_custom: false,
+                highlight: entry.highlight,
+                catalogs: merged.catalogs,
+                description: entry.description,
+                tags: entry.tags,
+                category: entry.category,
+                maintainer: entry.maintainer,
+                license: entry.license,
+                verified: entry.verified,
+                official: entry.official,
+                skills_path: entry.skills_path,
+                installs: None,
+                recent_installs: None,
+            });
         }
     }
 
@@ -148,16 +384,31 @@ pub async fn get_all_repos(app: tauri::AppHandle) -> Result<Vec<RepoInfo>, Strin
         );
         if !exists {
             let repo_key = format!("{}/{}", custom_repo.owner, custom_repo.repo);
-            repos.push(RepoInfo {
-                owner: custom_repo.owner,
-                repo: custom_repo.repo,
-                is_fetched: fetched_repos.repos.contains_key(&repo_key),
-                is_custom: true,
-                highlight: false,
-            });
+            let is_fetched = fetched_repos.repos.contains_key(&repo_key);
+            repos.push(RepoInfo::custom(custom_repo.owner, custom_repo.repo, is_fetched));
         }
     }
 
+    if let Some(filter) = filter {
+        repos.retain(|r| filter.matches(r));
+    }
+
+    if let Some(popularity) = Popularity::load() {
+        for info in &mut repos {
+            (info.installs, info.recent_installs) = match popularity.for_repo(&info.owner, &info.repo) {
+                Some((installs, recent)) => (Some(installs), recent),
+                None => (None, None),
+            };
+        }
+    }
+    // Repos without counts keep their catalog order after the ranked ones
+    match sort.as_deref() {
+        None => {}
+        Some("popular") => repos.sort_by_key(|r| std::cmp::Reverse(r.installs)),
+        Some("trending") => repos.sort_by_key(|r| std::cmp::Reverse(r.recent_installs)),
+        Some(other) => return Err(format!("Unknown sort '{}', expected popular or trending", other)),
+    }
+
     Ok(repos)
 }
 
@@ -166,35 +417,62 @@ pub async fn get_all_repos(app: tauri::AppHandle) -> Result<Vec<RepoInfo>, Strin
 struct RepoSource {
     owner: String,
     repo: String,
+    skills_path: Option<String>, // declared by the catalog, auto-detected when None
 }
 
 fn scan_repo_for_skills(
     source: &RepoSource,
     installed_skills: &[String],
 ) -> Vec<Skill> {
-    let mut skills = Vec::new();
     let data_path = get_data_path();
     let repo_path = data_path
         .join("repos")
         .join(&source.owner)
         .join(&source.repo);
 
+    scan_repo_dir(&repo_path, &source.owner, &source.repo, source.skills_path.as_deref(), installed_skills)
+}
+
+// Scan a checked out repo (cached or pinned) for skill folders, under the skills path
+// `catalog` (the merged catalog) declares for it
+pub(crate) fn scan_skills_in(
+    repo_path: &Path,
+    owner: &str,
+    repo: &str,
+    installed_skills: &[String],
+    catalog: &[MergedRepo],
+) -> Vec<Skill> {
+    let skills_path = catalog_skills_path(catalog, owner, repo);
+    scan_repo_dir(repo_path, owner, repo, skills_path.as_deref(), installed_skills)
+}
+
+/// Skills in a checked out repo, under `skills_path` when it names a real folder, else
+/// auto-detected. A repo whose root is the only skill yields it with path ".".
+/// The catalog builder uses this too, so the app and the skill index agree.
+pub(crate) fn scan_repo_dir(
+    repo_path: &Path,
+    owner: &str,
+    repo: &str,
+    skills_path: Option<&str>,
+    installed_skills: &[String],
+) -> Vec<Skill> {
+    let mut skills = Vec::new();
+
     if !repo_path.exists() {
         return skills;
     }
 
-    // Auto-detect skills_path
-    let skills_path = detect_skills_path(&repo_path);
+    let skills_path = skills_path
+        .map(|p| p.trim_end_matches('/'))
+        .filter(|p| parse_relative_path(p).is_ok_and(|relative| repo_path.join(relative).is_dir()))
+        .map(|p| if p.is_empty() { ".".to_string() } else { p.to_string() })
+        .unwrap_or_else(|| detect_skills_path(repo_path));
     let skills_dir = if skills_path == "." {
-        repo_path.clone()
+        repo_path.to_path_buf()
     } else {
         repo_path.join(&skills_path)
     };
 
-    if !skills_dir.exists() {
-        return skills;
-    }
-
     if let Ok(entries) = fs::read_dir(&skills_dir) {
         for entry in entries.filter_map(|e| e.ok()) {
             let entry_path = entry.path();
@@ -202,80 +480,192 @@ fn scan_repo_for_skills(
                 continue;
             }
 
-            let skill_file = if entry_path.join("SKILL.md").exists() {
-                entry_path.join("SKILL.md")
-            } else if entry_path.join("skill.md").exists() {
-                entry_path.join("skill.md")
-            } else {
+            let Some(skill_file) = find_skill_file(&entry_path) else {
                 continue;
             };
 
-            let content = fs::read_to_string(&skill_file).ok();
             let skill_folder = entry.file_name().to_string_lossy().to_string();
+            let id = format!("{}/{}/{}", owner, repo, skill_folder);
+            let mut skill = read_skill(&skill_file, id, owner, repo, &skills_path, skill_folder);
+            skill.is_installed = installed_skills.iter().any(|s| s == &skill.name || s == &skill.path);
+            skills.push(skill);
+        }
+    }
+
+    // Single-skill repos keep SKILL.md at the root
+    if skills.is_empty() {
+        if let Some(skill_file) = find_skill_file(repo_path) {
+            let mut skill = read_skill(&skill_file, String::new(), owner, repo, ".", ".".to_string());
+            let folder = if SkillName::parse(&skill.name).is_ok() { skill.name.clone() } else { repo.to_string() };
+            skill.id = format!("{}/{}/{}", owner, repo, folder);
+            skill.is_installed = installed_skills.iter().any(|s| s == &folder);
+            skills.push(skill);
+        }
+    }
 
-            // Parse frontmatter for name and description
-            let (name, description) = if let Some(ref c) = content {
-                parse_frontmatter(c)
+    skills
+}
+
+fn read_skill(skill_file: &Path, id: String, owner: &str, repo: &str, skills_path: &str, path: String) -> Skill {
+    let content = fs::read_to_string(skill_file).ok();
+
+    // Parse frontmatter for name, description and dependencies
+    let (name, description, dependencies) = if let Some(ref c) = content {
+        let frontmatter = parse_frontmatter(c);
+        (frontmatter.name, frontmatter.description, frontmatter.dependencies)
+    } else {
+        (path.clone(), String::new(), Vec::new())
+    };
+
+    Skill {
+        id,
+        name,
+        description,
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+        skills_path: skills_path.to_string(),
+        path,
+        content,
+        is_installed: false,
+        is_fetched: true,
+        dependencies,
+        is_disabled: false,
+        installs: None,
+        recent_installs: None,
+    }
+}
+
+// Skills the precomputed index lists for a repo that has not been fetched yet
+fn indexed_skills(index: &SkillIndex, source: &RepoSource, installed_skills: &[String]) -> Vec<Skill> {
+    let same_repo = |owner: &str, repo: &str| {
+        owner.eq_ignore_ascii_case(&source.owner) && repo.eq_ignore_ascii_case(&source.repo)
+    };
+    // Older indexes leave the skills path out for repos using "skills/"
+    let repo_skills_path = index
+        .repositories
+        .iter()
+        .find(|r| same_repo(&r.owner, &r.repo))
+        .and_then(|r| r.skills_path.as_deref())
+        .unwrap_or("skills")
+        .trim_end_matches('/');
+
+    index
+        .skills
+        .iter()
+        .filter(|entry| same_repo(&entry.owner, &entry.repo))
+        .map(|entry| {
+            let is_root = entry.path == ".";
+            // Same id a scan produces once the repo is fetched, so favorites carry over
+            let id = if is_root {
+                format!("{}/{}/{}", source.owner, source.repo, entry.name)
             } else {
-                (skill_folder.clone(), String::new())
+                format!("{}/{}/{}", source.owner, source.repo, entry.path)
             };
+            let skills_path = if is_root || repo_skills_path.is_empty() { "." } else { repo_skills_path };
 
-            let id = format!("{}/{}/{}", source.owner, source.repo, skill_folder);
-            let is_installed = installed_skills.iter().any(|s| s == &name || s == &skill_folder);
-
-            skills.push(Skill {
+            Skill {
                 id,
-                name,
-                description,
+                name: entry.name.clone(),
+                description: entry.description.clone(),
                 owner: source.owner.clone(),
                 repo: source.repo.clone(),
-                skills_path: skills_path.clone(),
-                path: skill_folder,
-                content,
-                is_installed,
-                is_fetched: true,
-            });
-        }
-    }
+                skills_path: skills_path.to_string(),
+                path: entry.path.clone(),
+                content: None,
+                is_installed: installed_skills.iter().any(|s| s == &entry.name || s == &entry.path),
+                is_fetched: false,
+                dependencies: Vec::new(),
+                is_disabled: false,
+                installs: None,
+                recent_installs: None,
+            }
+        })
+        .collect()
+}
 
-    skills
+fn list_dir_names(path: &Path) -> Vec<String> {
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Folder names currently present in any enabled agent's skills directory
+pub(crate) fn list_installed_skill_names() -> Vec<String> {
+    let mut names: Vec<String> = user_targets()
+        .iter()
+        .flat_map(|t| list_dir_names(&t.root))
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+// Folder names of skills disabled in any enabled agent
+pub(crate) fn list_disabled_skill_names() -> Vec<String> {
+    let mut names: Vec<String> = user_targets()
+        .iter()
+        .flat_map(|t| list_dir_names(&t.disabled_root()))
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+// Installed folder of a skill, matched by frontmatter name or folder name
+pub(crate) fn installed_skill_dir(skill: &Skill) -> Option<PathBuf> {
+    let targets = user_targets();
+    [&skill.name, &skill.path]
+        .into_iter()
+        .filter(|name| SkillName::parse(name).is_ok())
+        .flat_map(|name| targets.iter().map(move |t| t.root.join(name)))
+        .find(|path| path.exists())
+}
+
+// Folder of a skill inside the repo cache
+pub(crate) fn cached_skill_dir(skill: &Skill) -> Result<PathBuf, String> {
+    Ok(resolve_repo_path(&skill.owner, &skill.repo)?
+        .join(parse_relative_path(&skill.skills_path)?)
+        .join(parse_relative_path(&skill.path)?))
+}
+
+// SKILL.md (or skill.md) inside a skill folder
+pub(crate) fn find_skill_file(dir: &Path) -> Option<PathBuf> {
+    ["SKILL.md", "skill.md"]
+        .into_iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.exists())
 }
 
 #[tauri::command]
-pub async fn get_all_skills(app: tauri::AppHandle) -> Result<Vec<Skill>, String> {
-    let library_path = get_library_path(&app);
-    let catalog_path = library_path.join("catalog.json");
+pub async fn get_all_skills() -> Result<Vec<Skill>, String> {
+    Ok(collect_all_skills())
+}
 
+// All skills from catalog and custom repos, with installed status. Repos that have not
+// been fetched contribute the skills listed in the skill index, marked as not fetched.
+pub(crate) fn collect_all_skills() -> Vec<Skill> {
     let fetched_repos = load_fetched_repos();
     let custom_repos = load_custom_repos();
-    let installed_path = get_installed_skills_path();
-
-    let installed_skills: Vec<String> = if installed_path.exists() {
-        fs::read_dir(&installed_path)
-            .map(|entries| {
-                entries
-                    .filter_map(|e| e.ok())
-                    .map(|e| e.file_name().to_string_lossy().to_string())
-                    .collect()
-            })
-            .unwrap_or_default()
-    } else {
-        vec![]
-    };
+    let installed_skills = list_installed_skill_names();
+    let index = load_skill_index();
 
     // Collect all repo sources (catalog + custom)
     let mut all_sources: Vec<RepoSource> = Vec::new();
 
     // Add catalog repos
-    if let Ok(catalog_content) = fs::read_to_string(&catalog_path) {
-        if let Ok(catalog) = serde_json::from_str::<Catalog>(&catalog_content) {
-            for catalog_repo in catalog.repos {
-                if let Some((owner, repo)) = catalog_repo.url.split_once('/') {
-                    all_sources.push(RepoSource {
-                        owner: owner.to_string(),
-                        repo: repo.to_string(),
-                    });
-                }
+    if let Ok(catalog) = load_catalog() {
+        for catalog_repo in catalog.repos {
+            if let Some((owner, repo)) = catalog_repo.url.split_once('/') {
+                all_sources.push(RepoSource {
+                    owner: owner.to_string(),
+                    repo: repo.to_string(),
+                    skills_path: catalog_repo.skills_path.clone(),
+                });
             }
         }
     }
@@ -289,6 +679,7 @@ pub async fn get_all_skills(app: tauri::AppHandle) -> Result<Vec<Skill>, String>
             all_sources.push(RepoSource {
                 owner: custom_repo.owner,
                 repo: custom_repo.repo,
+                skills_path: None,
             });
         }
     }
@@ -306,6 +697,12 @@ pub async fn get_all_skills(app: tauri::AppHandle) -> Result<Vec<Skill>, String>
                 // Update installed status
                 for skill in &mut cached_skills {
                     skill.is_installed = installed_skills.iter().any(|s| s == &skill.name || s == &skill.path);
+                    // Caches written before dependencies were tracked
+                    if skill.dependencies.is_empty() {
+                        if let Some(ref c) = skill.content {
+                            skill.dependencies = parse_frontmatter(c).dependencies;
+                        }
+                    }
                 }
                 skills.extend(cached_skills);
             } else {
@@ -314,10 +711,37 @@ pub async fn get_all_skills(app: tauri::AppHandle) -> Result<Vec<Skill>, String>
                 let _ = save_cached_skills(&source.owner, &source.repo, &repo_skills);
                 skills.extend(repo_skills);
             }
+        } else if let Some(ref index) = index {
+            skills.extend(indexed_skills(index, &source, &installed_skills));
         }
     }
 
-    Ok(skills)
+    // Disabled skills are not active for any agent, so they count as not installed
+    let disabled_skills = list_disabled_skill_names();
+    for skill in &mut skills {
+        skill.is_disabled = !skill.is_installed
+            && disabled_skills.iter().any(|s| s == &skill.name || s == &skill.path);
+    }
+
+    if let Some(popularity) = Popularity::load() {
+        popularity.apply(&mut skills);
+    }
+
+    skills
+}
+
+/// A skill with its SKILL.md content, fetching its repo first when it is only known from the index.
+#[tauri::command]
+pub async fn open_skill(skill_id: String) -> Result<Skill, String> {
+    let find = |skills: Vec<Skill>| skills.into_iter().find(|s| s.id == skill_id);
+    let skill = find(collect_all_skills()).ok_or_else(|| format!("Skill {} not found", skill_id))?;
+    if skill.is_fetched {
+        return Ok(skill);
+    }
+
+    fetch_repo_to_cache(&skill.owner, &skill.repo, &load_merged_catalog().0)?;
+    find(collect_all_skills())
+        .ok_or_else(|| format!("Skill {} not found in {}/{}", skill_id, skill.owner, skill.repo))
 }
 
 #[tauri::command]
@@ -330,9 +754,16 @@ pub async fn fetch_repo(
     owner: String,
     repo: String,
 ) -> Result<String, String> {
-    let data_path = get_data_path();
-    let repo_path = data_path.join("repos").join(&owner).join(&repo);
-    let repo_url = format!("https://github.com/{}/{}.git", owner, repo);
+    let skills = fetch_repo_to_cache(&owner, &repo, &load_merged_catalog().0)?;
+    Ok(format!("Fetched {}/{} ({} skills)", owner, repo, skills.len()))
+}
+
+// Clone (or re-clone) a repo into the cache, scan it and record the fetch
+pub(crate) fn fetch_repo_to_cache(owner: &str, repo: &str, catalog: &[MergedRepo]) -> Result<Vec<Skill>, String> {
+    let repo_path = resolve_repo_path(owner, repo)?;
+    let repo_key = format!("{}/{}", owner, repo);
+    let lock = repo_lock(&repo_key);
+    let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
 
     if repo_path.exists() {
         // Remove and re-fetch for updates
@@ -340,35 +771,19 @@ pub async fn fetch_repo(
     }
 
     // Clone with minimal depth
-    if let Some(parent) = repo_path.parent() {
-        fs::create_dir_all(parent).map_err(|e| format!("Failed to create dir: {}", e))?;
-    }
-    Command::new("git")
-        .args(["clone", "--depth", "1", &repo_url, repo_path.to_str().unwrap()])
-        .output()
-        .map_err(|e| format!("Failed to clone: {}", e))?;
-
-    // Remove .git folder to save space
-    let git_dir = repo_path.join(".git");
-    if git_dir.exists() {
-        fs::remove_dir_all(&git_dir).ok(); // Ignore errors
-    }
+    let commit = shallow_clone(&github_url(owner, repo), &repo_path, None)?;
 
     // Scan and cache skills metadata
-    let source = RepoSource {
-        owner: owner.clone(),
-        repo: repo.clone(),
-    };
-    let skills = scan_repo_for_skills(&source, &[]);
-    save_cached_skills(&owner, &repo, &skills)?;
+    let skills = scan_skills_in(&repo_path, owner, repo, &[], catalog);
+    save_cached_skills(owner, repo, &skills)?;
 
     // Update fetched repos
-    let mut fetched = load_fetched_repos();
-    let repo_key = format!("{}/{}", owner, repo);
-    fetched.repos.insert(repo_key.clone(), chrono_now());
-    save_fetched_repos(&fetched)?;
+    update_fetched_repos(|fetched| {
+        fetched.repos.insert(repo_key.clone(), chrono_now());
+        fetched.record_commit(&repo_key, commit.sha, commit.committed_at, commit.history_len);
+    })?;
 
-    Ok(format!("Fetched {} ({} skills)", repo_key, skills.len()))
+    Ok(skills)
 }
 
 // Cache skills metadata to JSON
@@ -394,7 +809,7 @@ fn load_cached_skills(owner: &str, repo: &str) -> Option<Vec<Skill>> {
     }
 }
 
-fn chrono_now() -> String {
+pub(crate) fn chrono_now() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
     let duration = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -405,19 +820,28 @@ fn chrono_now() -> String {
 }
 
 #[tauri::command]
-pub async fn get_installed_skills() -> Result<Vec<String>, String> {
-    let installed_path = get_installed_skills_path();
-
-    if !installed_path.exists() {
-        return Ok(vec![]);
+pub async fn get_installed_skills(scope: Option<String>) -> Result<Vec<InstalledSkill>, String> {
+    let mut skills: Vec<InstalledSkill> = Vec::new();
+
+    for target in selected_targets(scope.as_deref())? {
+        for name in list_dir_names(&target.root) {
+            let status = TargetStatus {
+                agent: target.agent.to_string(),
+                scope: target.scope.to_string(),
+                path: target.root.join(&name).to_string_lossy().to_string(),
+                success: true,
+                error: None,
+            };
+            match skills.iter_mut().find(|s| s.name == name) {
+                Some(skill) => skill.targets.push(status),
+                None => skills.push(InstalledSkill {
+                    name,
+                    targets: vec![status],
+                }),
+            }
+        }
     }
 
-    let skills = fs::read_dir(&installed_path)
-        .map_err(|e| format!("Failed to read directory: {}", e))?
-        .filter_map(|e| e.ok())
-        .map(|e| e.file_name().to_string_lossy().to_string())
-        .collect();
-
     Ok(skills)
 }
 
@@ -466,33 +890,23 @@ pub async fn add_custom_repo(
     owner: String,
     repo: String,
 ) -> Result<String, String> {
-    let data_path = get_data_path();
-    let repo_path = data_path.join("repos").join(&owner).join(&repo);
-    let repo_url = format!("https://github.com/{}/{}.git", owner, repo);
+    let repo_path = resolve_repo_path(&owner, &repo)?;
+    let repo_key = format!("{}/{}", owner, repo);
+    let lock = repo_lock(&repo_key);
+    let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
 
     // Clone the repo
-    if !repo_path.exists() {
-        if let Some(parent) = repo_path.parent() {
-            fs::create_dir_all(parent).map_err(|e| format!("Failed to create dir: {}", e))?;
-        }
-        Command::new("git")
-            .args(["clone", "--depth", "1", &repo_url, repo_path.to_str().unwrap()])
-            .output()
-            .map_err(|e| format!("Failed to clone: {}", e))?;
-
-        // Remove .git folder to save space
-        let git_dir = repo_path.join(".git");
-        if git_dir.exists() {
-            fs::remove_dir_all(&git_dir).ok();
-        }
-    }
+    let commit = if repo_path.exists() {
+        None
+    } else {
+        Some(shallow_clone(&github_url(&owner, &repo), &repo_path, None)?)
+    };
 
     // Detect skills_path by looking for skill.md or SKILL.md files
     let skills_path = detect_skills_path(&repo_path);
 
     // Add to custom repos
     let mut custom = load_custom_repos();
-    let repo_key = format!("{}/{}", owner, repo);
 
     // Check if already exists
     if !custom.repos.iter().any(|r| r.owner == owner && r.repo == repo) {
@@ -505,14 +919,17 @@ pub async fn add_custom_repo(
     }
 
     // Update fetched repos
-    let mut fetched = load_fetched_repos();
-    fetched.repos.insert(repo_key.clone(), chrono_now());
-    save_fetched_repos(&fetched)?;
+    update_fetched_repos(|fetched| {
+        if let Some(commit) = commit {
+            fetched.record_commit(&repo_key, commit.sha, commit.committed_at, commit.history_len);
+        }
+        fetched.repos.insert(repo_key.clone(), chrono_now());
+    })?;
 
     Ok(format!("Added custom repo {}", repo_key))
 }
 
-fn detect_skills_path(repo_path: &PathBuf) -> String {
+fn detect_skills_path(repo_path: &Path) -> String {
     // Common skill paths to check
     let paths = ["skills", "src/skills", "lib/skills", "."];
 
@@ -521,7 +938,7 @@ fn detect_skills_path(repo_path: &PathBuf) -> String {
 
     for path in paths {
         let check_path = if path == "." {
-            repo_path.clone()
+            repo_path.to_path_buf()
         } else {
             repo_path.join(path)
         };
@@ -644,11 +1061,12 @@ pub async fn toggle_favorite_repo(repo_key: String) -> Result<Favorites, String>
 
 #[tauri::command]
 pub async fn reveal_skill_in_finder(skill_name: String) -> Result<(), String> {
-    let skill_path = get_installed_skills_path().join(&skill_name);
-
-    if !skill_path.exists() {
-        return Err(format!("Skill folder not found: {}", skill_name));
-    }
+    let skill_name = SkillName::parse(&skill_name)?;
+    let skill_path = user_targets()
+        .into_iter()
+        .map(|t| t.root.join(&skill_name))
+        .find(|path| path.exists())
+        .ok_or_else(|| format!("Skill folder not found: {}", skill_name))?;
 
     #[cfg(target_os = "macos")]
     {
@@ -682,8 +1100,7 @@ pub async fn get_repo_readme(
     owner: String,
     repo: String,
 ) -> Result<Option<String>, String> {
-    let data_path = get_data_path();
-    let repo_path = data_path.join("repos").join(&owner).join(&repo);
+    let repo_path = resolve_repo_path(&owner, &repo)?;
 
     if !repo_path.exists() {
         return Ok(None);
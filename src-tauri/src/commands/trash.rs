@@ -0,0 +1,162 @@
+use super::agents::InstallTarget;
+use super::paths::{get_data_path, SkillName};
+use super::settings::load_settings;
+use crate::models::TrashEntry;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const METADATA_FILE: &str = "trash.json";
+const CONTENT_DIR: &str = "content";
+
+fn get_trash_path() -> PathBuf {
+    get_data_path().join("trash")
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// Rename when possible, otherwise copy and delete (e.g. across filesystems)
+fn move_path(from: &Path, to: &Path) -> std::io::Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    if from.is_dir() {
+        copy_all(from, to)?;
+        fs::remove_dir_all(from)
+    } else {
+        fs::copy(from, to)?;
+        fs::remove_file(from)
+    }
+}
+
+fn copy_all(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let dest = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_all(&entry.path(), &dest)?;
+        } else {
+            fs::copy(entry.path(), &dest)?;
+        }
+    }
+    Ok(())
+}
+
+/// Move an installed skill folder into the trash and record where it came from.
+pub(crate) fn move_to_trash(
+    target: &InstallTarget,
+    installed_path: &Path,
+    skill_name: &SkillName,
+) -> Result<TrashEntry, String> {
+    let trashed_at = now_secs();
+    let trash_path = get_trash_path();
+
+    let mut id = format!("{}-{}-{}", trashed_at, target.agent, skill_name);
+    let mut suffix = 1;
+    while trash_path.join(&id).exists() {
+        id = format!("{}-{}-{}-{}", trashed_at, target.agent, skill_name, suffix);
+        suffix += 1;
+    }
+
+    let entry_path = trash_path.join(&id);
+    fs::create_dir_all(&entry_path).map_err(|e| format!("Failed to create trash folder: {}", e))?;
+
+    let entry = TrashEntry {
+        id,
+        skill_name: skill_name.to_string(),
+        agent: target.agent.to_string(),
+        scope: target.scope.to_string(),
+        original_path: installed_path.to_string_lossy().to_string(),
+        trashed_at,
+        expires_at: trashed_at + load_settings().trash_retention_days * 24 * 60 * 60,
+    };
+
+    if let Err(e) = move_path(installed_path, &entry_path.join(CONTENT_DIR)) {
+        fs::remove_dir_all(&entry_path).ok();
+        return Err(format!("Failed to move skill to trash: {}", e));
+    }
+
+    let metadata = serde_json::to_string_pretty(&entry).map_err(|e| e.to_string())?;
+    fs::write(entry_path.join(METADATA_FILE), metadata)
+        .map_err(|e| format!("Failed to write trash metadata: {}", e))?;
+
+    Ok(entry)
+}
+
+fn load_trash_entries() -> Vec<TrashEntry> {
+    let Ok(entries) = fs::read_dir(get_trash_path()) else {
+        return Vec::new();
+    };
+
+    let mut trash: Vec<TrashEntry> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| fs::read_to_string(e.path().join(METADATA_FILE)).ok())
+        .filter_map(|c| serde_json::from_str(&c).ok())
+        .collect();
+    trash.sort_by_key(|e| std::cmp::Reverse(e.trashed_at));
+    trash
+}
+
+fn trash_entry_path(id: &str) -> Result<PathBuf, String> {
+    Ok(get_trash_path().join(SkillName::parse(id)?))
+}
+
+/// Delete trash entries past their retention period.
+pub(crate) fn purge_expired_trash() {
+    let now = now_secs();
+    for entry in load_trash_entries() {
+        if entry.expires_at <= now {
+            if let Ok(path) = trash_entry_path(&entry.id) {
+                fs::remove_dir_all(path).ok();
+            }
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn list_trash() -> Result<Vec<TrashEntry>, String> {
+    purge_expired_trash();
+    Ok(load_trash_entries())
+}
+
+#[tauri::command]
+pub async fn restore_skill(id: String) -> Result<TrashEntry, String> {
+    let entry_path = trash_entry_path(&id)?;
+    let content = fs::read_to_string(entry_path.join(METADATA_FILE))
+        .map_err(|_| format!("Trash entry '{}' not found", id))?;
+    let entry: TrashEntry = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse trash metadata: {}", e))?;
+
+    let original_path = PathBuf::from(&entry.original_path);
+    if original_path.symlink_metadata().is_ok() {
+        return Err(format!(
+            "Cannot restore '{}': {} already exists",
+            entry.skill_name, entry.original_path
+        ));
+    }
+    if let Some(parent) = original_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+
+    move_path(&entry_path.join(CONTENT_DIR), &original_path)
+        .map_err(|e| format!("Failed to restore skill: {}", e))?;
+    fs::remove_dir_all(&entry_path).ok();
+
+    Ok(entry)
+}
+
+#[tauri::command]
+pub async fn empty_trash() -> Result<(), String> {
+    let trash_path = get_trash_path();
+    if trash_path.exists() {
+        fs::remove_dir_all(&trash_path).map_err(|e| format!("Failed to empty trash: {}", e))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,439 @@
+use super::agents::{selected_targets, user_targets, InstallTarget};
+use super::bulk::{run_batch, summarize, update_from_cache, Progress};
+use super::catalog::load_merged_catalog;
+use super::hashing::{file_hashes, hash_dir};
+use super::install::{load_install_records, InstallRecord};
+use super::paths::{get_data_path, SkillName};
+use super::skills::{cached_skill_dir, collect_all_skills, fetch_repo_to_cache, find_skill_file, load_fetched_repos, parse_frontmatter};
+use super::trash::purge_expired_trash;
+use crate::models::{BulkItemResult, BulkSummary, FrontmatterChange, Skill, SkillUpdate};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Result of the last check, so update_all knows which skills were unmodified before the refresh
+fn get_updates_path() -> PathBuf {
+    get_data_path().join("updates.json")
+}
+
+fn load_saved_updates() -> Option<Vec<SkillUpdate>> {
+    fs::read_to_string(get_updates_path())
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+}
+
+fn save_updates(updates: &[SkillUpdate]) -> Result<(), String> {
+    fs::create_dir_all(get_data_path()).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(updates).map_err(|e| e.to_string())?;
+    fs::write(get_updates_path(), content).map_err(|e| format!("Failed to save update check: {}", e))
+}
+
+pub(crate) fn scope_targets(scope: Option<&str>) -> Result<Vec<InstallTarget>, String> {
+    match scope {
+        Some(scope) => selected_targets(Some(scope)),
+        None => Ok(user_targets()),
+    }
+}
+
+// First installed copy of a skill, enabled or disabled, under its name or repo folder,
+// with its enabled path (the key of its install record)
+fn find_installed(skill: &Skill, targets: &[InstallTarget]) -> Option<(PathBuf, PathBuf)> {
+    [&skill.name, &skill.path]
+        .into_iter()
+        .filter(|name| SkillName::parse(name).is_ok())
+        .flat_map(|name| {
+            targets.iter().flat_map(move |t| {
+                let enabled = t.root.join(name);
+                [(enabled.clone(), enabled.clone()), (t.disabled_root().join(name), enabled)]
+            })
+        })
+        .find(|(path, _)| path.is_dir())
+}
+
+fn frontmatter_changes(installed: &Path, upstream: &Path) -> Vec<FrontmatterChange> {
+    let read = |dir: &Path| {
+        find_skill_file(dir)
+            .and_then(|file| fs::read_to_string(file).ok())
+            .map(|content| parse_frontmatter(&content))
+    };
+    let (Some(old), Some(new)) = (read(installed), read(upstream)) else {
+        return Vec::new();
+    };
+
+    [
+        ("name", old.name, new.name),
+        ("description", old.description, new.description),
+        ("dependencies", old.dependencies.join(", "), new.dependencies.join(", ")),
+    ]
+    .into_iter()
+    .filter(|(_, old_value, new_value)| old_value != new_value)
+    .map(|(field, old_value, new_value)| FrontmatterChange {
+        field: field.to_string(),
+        old_value,
+        new_value,
+    })
+    .collect()
+}
+
+/// An installed copy of a skill found by the update check.
+struct InstalledCopy<'a> {
+    path: PathBuf,
+    hash: String,
+    record: Option<&'a InstallRecord>,
+    is_modified: bool,
+}
+
+// An installed copy is unmodified if it still has the content it was installed with.
+// Copies installed before records were kept are unmodified if they match `upstream`
+// before refreshing, or were already found unmodified by an earlier check
+fn installed_copy<'a>(
+    skill: &Skill,
+    targets: &[InstallTarget],
+    records: &'a HashMap<String, InstallRecord>,
+    previous: &[SkillUpdate],
+    upstream: Option<&Path>,
+) -> Option<InstalledCopy<'a>> {
+    let (path, enabled_path) = find_installed(skill, targets)?;
+    let hash = hash_dir(&path).ok()?;
+    let record = records.get(enabled_path.to_string_lossy().as_ref());
+    let is_modified = match record {
+        Some(record) => record.content_hash != hash,
+        None => {
+            let matches_cache = upstream
+                .and_then(|dir| hash_dir(dir).ok())
+                .is_some_and(|cached| cached == hash);
+            let known_unmodified = previous
+                .iter()
+                .any(|u| u.skill_id == skill.id && u.installed_hash == hash && !u.is_modified);
+            !(matches_cache || known_unmodified)
+        }
+    };
+
+    Some(InstalledCopy {
+        path,
+        hash,
+        record,
+        is_modified,
+    })
+}
+
+// Compare an installed copy with the upstream folder; None when identical
+fn compare(
+    skill: &Skill,
+    installed: &InstalledCopy,
+    upstream: &Path,
+    upstream_commit: Option<String>,
+) -> Result<Option<SkillUpdate>, String> {
+    let upstream_hash = hash_dir(upstream)?;
+    if upstream_hash == installed.hash {
+        return Ok(None);
+    }
+
+    let old_files = file_hashes(&installed.path)?;
+    let new_files = file_hashes(upstream)?;
+
+    Ok(Some(SkillUpdate {
+        skill_id: skill.id.clone(),
+        name: skill.name.clone(),
+        installed_path: installed.path.to_string_lossy().to_string(),
+        installed_hash: installed.hash.clone(),
+        upstream_hash,
+        installed_commit: installed.record.and_then(|r| r.commit.clone()),
+        upstream_commit,
+        is_modified: installed.is_modified,
+        added_files: new_files.keys().filter(|f| !old_files.contains_key(*f)).cloned().collect(),
+        removed_files: old_files.keys().filter(|f| !new_files.contains_key(*f)).cloned().collect(),
+        modified_files: new_files
+            .iter()
+            .filter(|(f, hash)| old_files.get(*f).is_some_and(|old| old != *hash))
+            .map(|(f, _)| f.clone())
+            .collect(),
+        frontmatter_changes: frontmatter_changes(&installed.path, upstream),
+    }))
+}
+
+pub(crate) fn run_check(
+    app: Option<&tauri::AppHandle>,
+    targets: &[InstallTarget],
+    refresh: bool,
+) -> Result<Vec<SkillUpdate>, String> {
+    let previous = load_saved_updates().unwrap_or_default();
+    let records = load_install_records();
+
+    let mut installed: HashMap<String, InstalledCopy> = HashMap::new();
+    let mut repos: Vec<String> = Vec::new();
+    // Skills only known from the index have no cache to compare against
+    for skill in collect_all_skills().into_iter().filter(|s| s.is_fetched) {
+        let upstream = cached_skill_dir(&skill).ok();
+        let Some(copy) = installed_copy(&skill, targets, &records, &previous, upstream.as_deref()) else {
+            continue;
+        };
+        repos.push(format!("{}/{}", skill.owner, skill.repo));
+        installed.insert(skill.id.clone(), copy);
+    }
+
+    if refresh {
+        repos.sort();
+        repos.dedup();
+        let progress = Progress {
+            app,
+            operation: "refresh",
+            total: repos.len(),
+        };
+        // A repo that fails to refresh is compared against its existing cache
+        let catalog = load_merged_catalog().0;
+        run_batch(&progress, &repos, 0, None, |key| {
+            let (owner, repo) = key.split_once('/').ok_or("Invalid repo")?;
+            fetch_repo_to_cache(owner, repo, &catalog).map(|_| ())
+        });
+    }
+
+    let commits = load_fetched_repos().commits;
+    let mut updates = Vec::new();
+    for skill in collect_all_skills() {
+        let Some(copy) = installed.get(&skill.id) else {
+            continue;
+        };
+        let Ok(upstream) = cached_skill_dir(&skill) else {
+            continue;
+        };
+        let upstream_commit = commits.get(&format!("{}/{}", skill.owner, skill.repo)).cloned();
+        if let Ok(Some(update)) = compare(&skill, copy, &upstream, upstream_commit) {
+            updates.push(update);
+        }
+    }
+    updates.sort_by(|a, b| a.name.cmp(&b.name));
+
+    save_updates(&updates)?;
+    Ok(updates)
+}
+
+/// Refresh the repos of installed skills (unless `refresh` is false) and list
+/// every installed skill that differs from the latest upstream version.
+#[tauri::command]
+pub async fn check_updates(
+    app: tauri::AppHandle,
+    scope: Option<String>,
+    refresh: Option<bool>,
+) -> Result<Vec<SkillUpdate>, String> {
+    let targets = scope_targets(scope.as_deref())?;
+
+    tauri::async_runtime::spawn_blocking(move || run_check(Some(&app), &targets, refresh.unwrap_or(true)))
+        .await
+        .map_err(|e| format!("Update check failed: {}", e))?
+}
+
+/// Apply every update found by the last check to skills without local changes.
+#[tauri::command]
+pub async fn update_all(
+    app: tauri::AppHandle,
+    scope: Option<String>,
+    concurrency: Option<usize>,
+) -> Result<BulkSummary, String> {
+    let targets = scope_targets(scope.as_deref())?;
+    purge_expired_trash();
+
+    tauri::async_runtime::spawn_blocking(move || apply_updates(Some(&app), &targets, concurrency))
+        .await
+        .map_err(|e| format!("Update failed: {}", e))?
+}
+
+// Skills whose update can be applied: unmodified when checked and not edited since
+fn unmodified_updates(updates: &[SkillUpdate]) -> Vec<String> {
+    updates
+        .iter()
+        .filter(|u| !u.is_modified)
+        .filter(|u| hash_dir(Path::new(&u.installed_path)).is_ok_and(|h| h == u.installed_hash))
+        .map(|u| u.skill_id.clone())
+        .collect()
+}
+
+// Updates still pending once `results` were applied
+fn remaining_updates(updates: Vec<SkillUpdate>, results: &[BulkItemResult]) -> Vec<SkillUpdate> {
+    updates
+        .into_iter()
+        .filter(|u| !results.iter().any(|r| r.item == u.skill_id && r.success))
+        .collect()
+}
+
+pub(crate) fn apply_updates(
+    app: Option<&tauri::AppHandle>,
+    targets: &[InstallTarget],
+    concurrency: Option<usize>,
+) -> Result<BulkSummary, String> {
+    let updates = match load_saved_updates() {
+        Some(updates) => updates,
+        None => run_check(app, targets, true)?,
+    };
+
+    let skill_ids = unmodified_updates(&updates);
+    let skills = collect_all_skills();
+    let progress = Progress {
+        app,
+        operation: "update",
+        total: skill_ids.len(),
+    };
+    let results = update_from_cache(&progress, &skills, &skill_ids, targets, concurrency);
+    save_updates(&remaining_updates(updates, &results))?;
+
+    Ok(summarize("update", results))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::paths::test_dir;
+
+    fn skill() -> Skill {
+        Skill {
+            id: "owner/repo/demo".to_string(),
+            name: "demo".to_string(),
+            description: String::new(),
+            owner: "owner".to_string(),
+            repo: "repo".to_string(),
+            skills_path: "skills".to_string(),
+            path: "demo".to_string(),
+            content: None,
+            is_installed: true,
+            is_fetched: true,
+            dependencies: Vec::new(),
+            is_disabled: false,
+            installs: None,
+            recent_installs: None,
+        }
+    }
+
+    fn write_skill(dir: &Path, description: &str, files: &[(&str, &str)]) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("SKILL.md"), format!("---\nname: demo\ndescription: {}\n---\n", description)).unwrap();
+        for (name, content) in files {
+            fs::write(dir.join(name), content).unwrap();
+        }
+    }
+
+    // An installed copy and an upstream copy of `demo`, both at `description` to begin with
+    fn fixture(label: &str) -> (PathBuf, Vec<InstallTarget>, PathBuf) {
+        let dir = test_dir(label);
+        let target = InstallTarget {
+            agent: "claude-code",
+            scope: "user",
+            root: dir.join("agent").join("skills"),
+        };
+        let upstream = dir.join("upstream");
+        write_skill(&target.root.join("demo"), "v1", &[("old.md", "old"), ("same.md", "same")]);
+        write_skill(&upstream, "v1", &[("old.md", "old"), ("same.md", "same")]);
+        (dir, vec![target], upstream)
+    }
+
+    fn record_install(targets: &[InstallTarget]) -> HashMap<String, InstallRecord> {
+        let path = targets[0].root.join("demo");
+        let record = InstallRecord {
+            content_hash: hash_dir(&path).unwrap(),
+            commit: Some("abc".to_string()),
+        };
+        HashMap::from([(path.to_string_lossy().to_string(), record)])
+    }
+
+    #[test]
+    fn up_to_date_copies_have_no_update() {
+        let (_dir, targets, upstream) = fixture("updates-current");
+        let records = record_install(&targets);
+
+        let copy = installed_copy(&skill(), &targets, &records, &[], Some(&upstream)).unwrap();
+        assert!(!copy.is_modified);
+        assert!(compare(&skill(), &copy, &upstream, None).unwrap().is_none());
+    }
+
+    #[test]
+    fn upstream_changes_are_listed_with_their_frontmatter() {
+        let (_dir, targets, upstream) = fixture("updates-changed");
+        let records = record_install(&targets);
+        fs::remove_file(upstream.join("old.md")).unwrap();
+        write_skill(&upstream, "v2", &[("new.md", "new")]);
+
+        let copy = installed_copy(&skill(), &targets, &records, &[], Some(&upstream)).unwrap();
+        let update = compare(&skill(), &copy, &upstream, Some("def".to_string())).unwrap().unwrap();
+        assert!(!update.is_modified);
+        assert_eq!(update.installed_commit.as_deref(), Some("abc"));
+        assert_eq!(update.upstream_commit.as_deref(), Some("def"));
+        assert_eq!(update.added_files, ["new.md"]);
+        assert_eq!(update.removed_files, ["old.md"]);
+        assert_eq!(update.modified_files, ["SKILL.md"]);
+
+        let changes: Vec<_> = update
+            .frontmatter_changes
+            .iter()
+            .map(|c| (c.field.as_str(), c.old_value.as_str(), c.new_value.as_str()))
+            .collect();
+        assert_eq!(changes, [("description", "v1", "v2")]);
+    }
+
+    #[test]
+    fn local_edits_are_modified() {
+        let (_dir, targets, upstream) = fixture("updates-modified");
+        let records = record_install(&targets);
+        fs::write(targets[0].root.join("demo").join("same.md"), "edited").unwrap();
+
+        let copy = installed_copy(&skill(), &targets, &records, &[], Some(&upstream)).unwrap();
+        assert!(copy.is_modified);
+        let update = compare(&skill(), &copy, &upstream, None).unwrap().unwrap();
+        assert!(update.is_modified);
+        assert_eq!(update.modified_files, ["same.md"]);
+        assert!(update.frontmatter_changes.is_empty());
+    }
+
+    #[test]
+    fn unrecorded_copies_fall_back_to_the_cache_and_earlier_checks() {
+        let (_dir, targets, upstream) = fixture("updates-unrecorded");
+        let records = HashMap::new();
+        let copy = installed_copy(&skill(), &targets, &records, &[], Some(&upstream)).unwrap();
+        assert!(!copy.is_modified);
+
+        // Once upstream moves on, only an earlier check can vouch for the copy
+        write_skill(&upstream, "v2", &[]);
+        let copy = installed_copy(&skill(), &targets, &records, &[], Some(&upstream)).unwrap();
+        assert!(copy.is_modified);
+
+        let mut earlier = compare(&skill(), &copy, &upstream, None).unwrap().unwrap();
+        earlier.is_modified = false;
+        let copy = installed_copy(&skill(), &targets, &records, &[earlier], Some(&upstream)).unwrap();
+        assert!(!copy.is_modified);
+    }
+
+    #[test]
+    fn only_unmodified_updates_are_applied() {
+        let (_dir, targets, upstream) = fixture("updates-apply");
+        let records = record_install(&targets);
+        write_skill(&upstream, "v2", &[]);
+        let copy = installed_copy(&skill(), &targets, &records, &[], Some(&upstream)).unwrap();
+        let update = compare(&skill(), &copy, &upstream, None).unwrap().unwrap();
+
+        let modified = SkillUpdate {
+            skill_id: "owner/repo/edited".to_string(),
+            is_modified: true,
+            ..update.clone()
+        };
+        let updates = vec![update.clone(), modified];
+        assert_eq!(unmodified_updates(&updates), ["owner/repo/demo"]);
+
+        // A copy edited after the check is left alone too
+        fs::write(targets[0].root.join("demo").join("same.md"), "edited").unwrap();
+        assert!(unmodified_updates(&updates).is_empty());
+
+        let results = [
+            BulkItemResult {
+                item: "owner/repo/demo".to_string(),
+                success: true,
+                error: None,
+            },
+            BulkItemResult {
+                item: "owner/repo/edited".to_string(),
+                success: false,
+                error: Some("failed".to_string()),
+            },
+        ];
+        let remaining = remaining_updates(updates, &results);
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].skill_id, "owner/repo/edited");
+    }
+}
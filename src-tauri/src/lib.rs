@@ -0,0 +1,97 @@
+mod commands;
+mod models;
+pub mod catalog_build;
+pub mod cli;
+
+use commands::{
+    get_all_skills, get_catalog, get_fetched_repos, get_installed_skills,
+    get_settings, install_skill, save_settings, uninstall_skill, fetch_repo,
+    add_custom_repo, remove_custom_repo, get_custom_repos, get_repo_readme, get_favorites,
+    toggle_favorite_skill, toggle_favorite_repo, reveal_skill_in_finder, get_all_repos,
+    get_skill_dependencies, get_skill_dependents, get_bundles, save_bundle, delete_bundle,
+    get_bundle_status, install_bundle, uninstall_bundle, export_lockfile, restore_from_lockfile,
+    get_agent_targets, get_skill_inventory, disable_skill, enable_skill,
+    list_trash, restore_skill, empty_trash, export_backup, import_backup,
+    bulk_install, bulk_uninstall, bulk_update, check_updates, update_all,
+    diff_skill_versions, list_skill_templates, create_skill,
+    list_forks, fork_skill, get_fork_status, merge_upstream,
+    get_catalog_info, get_catalog_sources, refresh_catalog, open_skill, check_catalog_health,
+    get_repo_metadata, refresh_popularity, get_ranked_skills, get_popularity_info,
+    publish_skill
+};
+use tauri::Manager;
+
+pub fn run() {
+    tauri::Builder::default()
+        .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_fs::init())
+        .setup(|app| {
+            if let Ok(resource_dir) = app.path().resource_dir() {
+                commands::skills::set_resource_dir(resource_dir);
+            }
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            get_catalog,
+            get_all_skills,
+            get_all_repos,
+            get_fetched_repos,
+            fetch_repo,
+            get_installed_skills,
+            get_settings,
+            save_settings,
+            install_skill,
+            uninstall_skill,
+            add_custom_repo,
+            remove_custom_repo,
+            get_custom_repos,
+            get_repo_readme,
+            get_favorites,
+            toggle_favorite_skill,
+            toggle_favorite_repo,
+            reveal_skill_in_finder,
+            get_skill_dependencies,
+            get_skill_dependents,
+            get_bundles,
+            save_bundle,
+            delete_bundle,
+            get_bundle_status,
+            install_bundle,
+            uninstall_bundle,
+            export_lockfile,
+            restore_from_lockfile,
+            get_agent_targets,
+            get_skill_inventory,
+            disable_skill,
+            enable_skill,
+            list_trash,
+            restore_skill,
+            empty_trash,
+            export_backup,
+            import_backup,
+            bulk_install,
+            bulk_uninstall,
+            bulk_update,
+            check_updates,
+            update_all,
+            diff_skill_versions,
+            list_skill_templates,
+            create_skill,
+            list_forks,
+            fork_skill,
+            get_fork_status,
+            merge_upstream,
+            get_catalog_info,
+            get_catalog_sources,
+            refresh_catalog,
+            open_skill,
+            check_catalog_health,
+            get_repo_metadata,
+            refresh_popularity,
+            get_ranked_skills,
+            get_popularity_info,
+            publish_skill,
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
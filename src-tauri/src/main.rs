@@ -1,39 +1,30 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-mod commands;
-mod models;
+fn main() {
+    // Any arguments select the headless CLI; old macOS launchers pass a -psn_ process id
+    let args: Vec<String> = std::env::args().skip(1).filter(|a| !a.starts_with("-psn_")).collect();
+    if args.is_empty() {
+        skill_studio_lib::run()
+    } else {
+        #[cfg(windows)]
+        attach_parent_console();
+        std::process::exit(skill_studio_lib::cli::run(&args))
+    }
+}
 
-use commands::{
-    get_all_skills, get_catalog, get_fetched_repos, get_installed_skills,
-    get_settings, install_skill, save_settings, uninstall_skill, fetch_repo,
-    add_custom_repo, remove_custom_repo, get_custom_repos, get_repo_readme, get_favorites,
-    toggle_favorite_skill, toggle_favorite_repo, reveal_skill_in_finder, get_all_repos
-};
+// Release builds use the windows subsystem and start without a console, so CLI output
+// would go nowhere; reuse the console of the shell that started us, if any
+#[cfg(windows)]
+fn attach_parent_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
 
-fn main() {
-    tauri::Builder::default()
-        .plugin(tauri_plugin_shell::init())
-        .plugin(tauri_plugin_fs::init())
-        .invoke_handler(tauri::generate_handler![
-            get_catalog,
-            get_all_skills,
-            get_all_repos,
-            get_fetched_repos,
-            fetch_repo,
-            get_installed_skills,
-            get_settings,
-            save_settings,
-            install_skill,
-            uninstall_skill,
-            add_custom_repo,
-            remove_custom_repo,
-            get_custom_repos,
-            get_repo_readme,
-            get_favorites,
-            toggle_favorite_skill,
-            toggle_favorite_repo,
-            reveal_skill_in_finder,
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    // Fails harmlessly when there is no parent console or one is already attached
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
 }
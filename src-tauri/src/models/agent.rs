@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentTarget {
+    pub id: String,
+    pub name: String,
+    pub user_dir: String,
+    pub project_dir: String, // relative to the project folder
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetStatus {
+    pub agent: String,
+    pub scope: String, // "user" or "project"
+    pub path: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledSkill {
+    pub name: String,
+    pub targets: Vec<TargetStatus>,
+}
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillTemplate {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub is_builtin: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSkillRequest {
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub template: Option<String>, // template id, "basic" when omitted
+    #[serde(default)]
+    pub include_scripts: bool,
+    #[serde(default)]
+    pub include_references: bool,
+    #[serde(default)]
+    pub install: bool, // write into the agent skills directories instead of the local workspace
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedSkill {
+    pub name: String,
+    pub paths: Vec<String>, // skill folders written
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishSkillRequest {
+    pub source: String, // skill folder, or the name of a skill in the local workspace
+    pub remote: String, // "owner/repo" on GitHub, or any git URL or path
+    #[serde(default)]
+    pub skills_path: Option<String>, // folder in the repo, from the catalog or detected when omitted
+    #[serde(default)]
+    pub branch: Option<String>, // new branch to push, "add-skill-<name>" (or "update-skill-<name>") when omitted
+    #[serde(default)]
+    pub base_branch: Option<String>, // branch to start from, the remote's default when omitted
+    #[serde(default)]
+    pub message: Option<String>, // commit message, generated when omitted
+    #[serde(default)]
+    pub open_pull_request: bool, // GitHub only, needs GITHUB_TOKEN or GH_TOKEN
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishResult {
+    pub name: String,
+    pub remote: String,
+    pub branch: String,
+    pub base_branch: Option<String>, // None when the repo had no commits yet
+    pub path: String,                // skill folder within the repo
+    pub commit: String,
+    pub is_update: bool, // the skill already existed on the base branch
+    pub files: Vec<String>,
+    pub pull_request_url: Option<String>,
+    pub pull_request_error: Option<String>, // the push succeeded but the pull request could not be opened
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+pub const BACKUP_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupManifest {
+    pub version: u32,
+    pub created_at: String,
+    pub skills: Vec<BackupSkill>,
+    pub config_files: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupSkill {
+    pub folder: String,
+    pub agent: String,
+    pub scope: String,
+    pub is_disabled: bool,
+    pub archive_path: String, // folder inside the archive
+    pub content_hash: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    pub restored: Vec<String>,
+    pub renamed: Vec<String>, // "old -> new"
+    pub skipped: Vec<String>,
+    pub failed: Vec<String>,
+    pub config_files: Vec<String>,
+}
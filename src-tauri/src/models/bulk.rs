@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+/// Emitted as `bulk-progress` when an item of a batch starts and when it finishes.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkProgress {
+    pub operation: String, // "install", "uninstall", "update", "refresh" or "health-check"
+    pub item: String,      // skill id, or "owner/repo" when refreshing
+    pub index: usize,
+    pub total: usize,
+    pub state: String, // "started", "succeeded" or "failed"
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkItemResult {
+    pub item: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkSummary {
+    pub operation: String,
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BulkItemResult>,
+}
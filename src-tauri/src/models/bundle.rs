@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Bundle {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub skills: Vec<BundleSkill>,
+    #[serde(default)]
+    pub is_catalog: bool, // defined in catalog.json rather than locally
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleSkill {
+    pub id: String, // "owner/repo/skill" format
+    #[serde(rename = "ref", default, skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>, // branch, tag or commit SHA
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum BundleMemberState {
+    Installed,
+    Missing,
+    Outdated,
+    Unavailable,
+    Error,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleMemberStatus {
+    pub id: String,
+    #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    pub name: Option<String>,
+    pub state: BundleMemberState,
+    pub message: Option<String>,
+}
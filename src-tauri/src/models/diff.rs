@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+/// One side of a skill diff.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum DiffSide {
+    // Installed copy, looked up in every enabled agent unless one is given
+    Installed {
+        skill_name: String,
+        agent: Option<String>,
+        scope: Option<String>,
+    },
+    // Latest fetched version in the repo cache
+    Cached { skill_id: String },
+    // Version at a branch, tag or commit of the skill's repo
+    Commit {
+        skill_id: String,
+        #[serde(rename = "ref")]
+        git_ref: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillDiff {
+    pub left_label: String,
+    pub right_label: String,
+    pub files: Vec<FileDiff>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDiff {
+    pub path: String,
+    pub status: String, // "added", "removed" or "modified"
+    pub is_binary: bool,
+    pub hunks: Vec<DiffHunk>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffHunk {
+    pub old_start: usize,
+    pub old_lines: usize,
+    pub new_start: usize,
+    pub new_lines: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLine {
+    pub kind: String, // "context", "added" or "removed"
+    pub content: String,
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+}
@@ -0,0 +1,31 @@
+use super::FileDiff;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkInfo {
+    pub name: String,
+    pub path: String,
+    pub upstream_id: String, // "owner/repo/skill" the fork was made from
+    pub upstream_name: String,
+    pub upstream_commit: Option<String>, // commit of the last fork or merge
+    pub forked_at: String,
+    pub merged_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkStatus {
+    pub fork: ForkInfo,
+    pub local_changes: Vec<FileDiff>,    // base -> fork
+    pub upstream_changes: Vec<FileDiff>, // base -> latest upstream
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeResult {
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+    pub conflicts: Vec<String>, // files left with conflict markers or kept as ours
+    pub upstream_commit: Option<String>,
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// Health of one catalog repo, with what to do about it if it is not "ok".
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoHealth {
+    pub repo: String,   // "owner/repo" as listed in the catalog
+    pub status: String, // "ok", "moved", "missing", "empty", "stale" or "error"
+    pub http_status: Option<u16>,
+    pub moved_to: Option<String>, // "owner/repo" GitHub redirects to
+    pub skill_count: Option<usize>,
+    pub last_commit: Option<String>,
+    pub last_commit_at: Option<String>, // Unix seconds, like other timestamps
+    pub days_since_commit: Option<u64>,
+    pub suggestion: Option<String>, // "remove", "update-url" or "review"
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthSummary {
+    pub total: usize,
+    pub ok: usize,
+    pub moved: usize,
+    pub missing: usize,
+    pub empty: usize,
+    pub stale: usize,
+    pub errors: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogHealthReport {
+    pub checked_at: String,
+    pub stale_days: u64,
+    pub summary: HealthSummary,
+    pub repos: Vec<RepoHealth>,
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// Precomputed names and descriptions of catalog skills, written by the `build-catalog` binary.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillIndex {
+    pub version: String,
+    pub last_updated: String,
+    #[serde(default)]
+    pub repositories: Vec<SkillRepository>,
+    #[serde(default)]
+    pub skills: Vec<SkillIndexEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillRepository {
+    pub owner: String,
+    pub repo: String,
+    pub branch: Option<String>,
+    pub skills_path: Option<String>, // e.g. "skills/" or "." for a single root skill
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillIndexEntry {
+    pub id: String, // "owner/repo/name" format
+    pub name: String,
+    pub description: String,
+    pub owner: String,
+    pub repo: String,
+    pub path: String, // skill folder inside the skills path, "." for a root skill
+    #[serde(default)]
+    pub local_path: String, // skill folder in the checkout the index was built from
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillOrigin {
+    pub skill_id: String,          // "owner/repo/skill" format
+    pub matched_by: String,        // "hash" or "name"
+    pub is_modified: Option<bool>, // differs from the cached upstream copy, None if not cached
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryEntry {
+    pub folder: String,
+    pub agent: String,
+    pub scope: String,
+    pub path: String,
+    pub is_symlink: bool,
+    pub is_disabled: bool, // moved to the agent's skills-disabled folder
+    pub is_valid: bool, // has a SKILL.md
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub dependencies: Vec<String>,
+    pub size_bytes: u64,
+    pub file_count: usize,
+    pub content_hash: Option<String>,
+    pub origin: Option<SkillOrigin>,
+    pub is_orphaned: bool, // no matching skill in any fetched repo
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+pub const LOCKFILE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Lockfile {
+    pub version: u32,
+    pub generated_at: String,
+    pub skills: Vec<LockedSkill>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedSkill {
+    pub id: String,            // "owner/repo/skill" format
+    pub source: String,        // git URL
+    pub commit: String,        // full commit SHA
+    pub path: String,          // skill folder relative to the repo root
+    pub content_hash: String,  // sha256 of the skill folder at `commit`
+    pub target: String,        // install target, e.g. "claude-code"
+    pub install_name: String,  // folder name inside the target
+}
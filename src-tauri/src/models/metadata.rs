@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// Repo details from the GitHub API, cached for a day.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHubRepoInfo {
+    pub stars: u64,
+    pub forks: u64,
+    pub open_issues: u64,
+    pub topics: Vec<String>,
+    pub license: Option<String>, // SPDX identifier
+    pub archived: bool,
+    pub default_branch: Option<String>,
+    pub pushed_at: Option<String>,
+    pub contributors: Option<usize>, // capped at 100
+    pub fetched_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoMetadata {
+    pub owner: String,
+    pub repo: String,
+    pub is_fetched: bool,
+    // Known locally once the repo is fetched
+    pub fetched_at: Option<String>,
+    pub license_file: Option<String>,
+    pub license_type: Option<String>, // detected from the license text, "Other" if unrecognized
+    pub last_commit: Option<String>,
+    pub last_commit_at: Option<String>, // Unix seconds
+    pub commit_count: Option<usize>,    // commits in the shallow history that was fetched
+    pub skill_count: usize,
+    pub file_count: usize,
+    pub total_size: u64, // bytes
+    // From the GitHub API, possibly cached; `github_error` is set when it could not be refreshed
+    pub github: Option<GitHubRepoInfo>,
+    pub github_error: Option<String>,
+}
@@ -1,3 +1,33 @@
 pub mod skill;
+pub mod agent;
+pub mod bundle;
+pub mod inventory;
+pub mod trash;
+pub mod backup;
+pub mod bulk;
+pub mod update;
+pub mod diff;
+pub mod authoring;
+pub mod fork;
+pub mod lockfile;
+pub mod index;
+pub mod health;
+pub mod metadata;
+pub mod popularity;
 
 pub use skill::*;
+pub use agent::*;
+pub use bundle::*;
+pub use inventory::*;
+pub use trash::*;
+pub use backup::*;
+pub use bulk::*;
+pub use update::*;
+pub use diff::*;
+pub use authoring::*;
+pub use fork::*;
+pub use lockfile::*;
+pub use index::*;
+pub use health::*;
+pub use metadata::*;
+pub use popularity::*;
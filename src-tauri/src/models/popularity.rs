@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// A skill's install count in a popularity feed. Skills are matched by `id`
+/// ("owner/repo/skill"), or by `source` ("owner/repo") and `name`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PopularityEntry {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub installs: u64,
+    #[serde(default)]
+    pub recent_installs: Option<u64>, // installs over the feed's recent window, e.g. the last week
+}
+
+/// Install counts from a skills.sh-style leaderboard.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PopularityFeed {
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    pub skills: Vec<PopularityEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PopularityInfo {
+    pub source: String,
+    pub fetched_at: String,
+    pub updated_at: Option<String>,
+    pub skill_count: usize,
+    pub previous_fetched_at: Option<String>, // snapshot trending counts are measured from, if the feed has none
+}
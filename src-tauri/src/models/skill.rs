@@ -1,3 +1,4 @@
+use super::{Bundle, TargetStatus};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -14,14 +15,50 @@ pub struct Skill {
     pub content: Option<String>,
     pub is_installed: bool,
     pub is_fetched: bool,
+    #[serde(default)]
+    pub dependencies: Vec<String>, // skill ids or names from frontmatter
+    #[serde(default)]
+    pub is_disabled: bool, // installed but moved aside so agents ignore it
+    #[serde(default)]
+    pub installs: Option<u64>, // from the popularity feed, if one is configured
+    #[serde(default)]
+    pub recent_installs: Option<u64>, // installs since the previous feed snapshot, or as the feed reports them
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyNode {
+    pub reference: String,
+    pub skill_id: Option<String>,
+    pub name: String,
+    pub is_installed: bool,
+    pub is_resolved: bool,
+    pub is_cycle: bool,
+    pub dependencies: Vec<DependencyNode>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Catalog {
     pub version: String,
     pub last_updated: String,
     pub repos: Vec<CatalogRepo>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bundles: Vec<Bundle>,
+}
+
+/// Which catalog is in use and where it came from.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogInfo {
+    pub name: String,   // catalog source name
+    pub source: String, // "remote", "bundled" or "file"
+    pub version: String,
+    pub last_updated: String,
+    pub url: Option<String>,
+    pub fetched_at: Option<String>,
+    pub repo_count: usize,
+    pub error: Option<String>, // set when the source could not be loaded
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -30,24 +67,126 @@ pub struct CatalogRepo {
     pub url: String,  // "owner/repo" format
     #[serde(default)]
     pub highlight: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maintainer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>, // SPDX identifier
+    #[serde(default)]
+    pub verified: bool, // reviewed by the catalog maintainers
+    #[serde(default)]
+    pub official: bool, // published by the tool or company the skills are for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skills_path: Option<String>, // overrides skills folder auto-detection
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct FetchedRepos {
     pub repos: HashMap<String, String>, // "owner/repo" -> lastFetched ISO date
+    #[serde(default)]
+    pub commits: HashMap<String, String>, // "owner/repo" -> fetched commit SHA
+    #[serde(default)]
+    pub commit_dates: HashMap<String, String>, // "owner/repo" -> fetched commit time, Unix seconds
+    #[serde(default)]
+    pub history_lens: HashMap<String, usize>, // "owner/repo" -> commits in the shallow clone
+}
+
+impl FetchedRepos {
+    pub fn record_commit(&mut self, repo_key: &str, sha: String, committed_at: String, history_len: usize) {
+        self.commits.insert(repo_key.to_string(), sha);
+        self.commit_dates.insert(repo_key.to_string(), committed_at);
+        self.history_lens.insert(repo_key.to_string(), history_len);
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Settings {
     pub install_method: String,
+    #[serde(default)]
+    pub npx_fallback: bool, // retry with `npx skills add` when the native install fails
+    #[serde(default = "default_enabled_agents")]
+    pub enabled_agents: Vec<String>, // agent ids to install into
+    #[serde(default)]
+    pub project_path: Option<String>, // project folder for project-scope installs
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u64, // uninstalled skills are purged from the trash after this
+    #[serde(default = "default_catalog_url")]
+    pub catalog_url: Option<String>, // remote catalog checked for updates, None to use the bundled one only
+    #[serde(default = "default_catalog_sources")]
+    pub catalog_sources: Vec<CatalogSource>, // merged in order, earlier sources take precedence
+    #[serde(default)]
+    pub index_url: Option<String>, // remote skill index checked for updates, None to use the bundled one only
+    #[serde(default)]
+    pub popularity_source: Option<String>, // URL or file path of an install-count feed
+}
+
+/// A catalog layered into the repo list: the official (bundled) catalog, a remote URL or a local file.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogSource {
+    pub name: String,             // unique, used to tag repos
+    pub kind: String,             // "bundled", "remote" or "file"
+    pub location: Option<String>, // URL or file path for remote and file sources
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_enabled_agents() -> Vec<String> {
+    vec!["claude-code".to_string()]
+}
+
+fn default_trash_retention_days() -> u64 {
+    30
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_catalog_sources() -> Vec<CatalogSource> {
+    vec![CatalogSource {
+        name: "official".to_string(),
+        kind: "bundled".to_string(),
+        location: None,
+        enabled: true,
+    }]
+}
+
+fn default_catalog_url() -> Option<String> {
+    Some("https://raw.githubusercontent.com/onmyway133/skill-studio/main/library/catalog.json".to_string())
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
             install_method: "copy".to_string(),
+            npx_fallback: false,
+            enabled_agents: default_enabled_agents(),
+            project_path: None,
+            trash_retention_days: default_trash_retention_days(),
+            catalog_url: default_catalog_url(),
+            catalog_sources: default_catalog_sources(),
+            index_url: None,
+            popularity_source: None,
         }
     }
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallResult {
+    pub skill_name: String,
+    pub method: String,             // "copy", "native" or "npx"
+    pub targets: Vec<TargetStatus>, // one entry per agent skills directory
+    pub files_written: Vec<String>,
+    pub dependencies: Vec<String>,  // dependencies installed alongside
+    pub warnings: Vec<String>,      // dependencies that could not be resolved
+    pub output: Option<String>,     // CLI output when installed via npx
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashEntry {
+    pub id: String,
+    pub skill_name: String,
+    pub agent: String,
+    pub scope: String,
+    pub original_path: String,
+    pub trashed_at: u64, // unix seconds
+    pub expires_at: u64, // unix seconds, purged automatically afterwards
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FrontmatterChange {
+    pub field: String, // "name", "description" or "dependencies"
+    pub old_value: String,
+    pub new_value: String,
+}
+
+/// An installed skill whose content differs from the latest cached upstream version.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillUpdate {
+    pub skill_id: String,
+    pub name: String,
+    pub installed_path: String,
+    pub installed_hash: String,
+    pub upstream_hash: String,
+    pub installed_commit: Option<String>, // upstream commit the installed copy came from, when recorded
+    pub upstream_commit: Option<String>,
+    pub is_modified: bool, // installed copy has local changes, so update_all leaves it alone
+    pub added_files: Vec<String>,
+    pub removed_files: Vec<String>,
+    pub modified_files: Vec<String>,
+    pub frontmatter_changes: Vec<FrontmatterChange>,
+}
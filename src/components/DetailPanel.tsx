@@ -165,7 +165,14 @@ export function DetailPanel({ selection, skills, installMethod, onRefresh, onTog
     const handleUninstallSkill = async (skill: Skill) => {
       setError(null);
       try {
-        await invoke("uninstall_skill", { skillName: skill.name });
+        const dependents = await invoke<string[]>("get_skill_dependents", { skillName: skill.name });
+        if (
+          dependents.length > 0 &&
+          !window.confirm(`${skill.name} is required by ${dependents.join(", ")}. Uninstall anyway?`)
+        ) {
+          return;
+        }
+        await invoke("uninstall_skill", { skillName: skill.name, force: true });
         onRefresh();
       } catch (e) {
         setError(String(e));
@@ -468,7 +475,14 @@ export function DetailPanel({ selection, skills, installMethod, onRefresh, onTog
   const handleUninstall = async () => {
     setError(null);
     try {
-      await invoke("uninstall_skill", { skillName: skill.name });
+      const dependents = await invoke<string[]>("get_skill_dependents", { skillName: skill.name });
+      if (
+        dependents.length > 0 &&
+        !window.confirm(`${skill.name} is required by ${dependents.join(", ")}. Uninstall anyway?`)
+      ) {
+        return;
+      }
+      await invoke("uninstall_skill", { skillName: skill.name, force: true });
       onRefresh();
     } catch (e) {
       setError(String(e));
@@ -515,7 +529,7 @@ export function DetailPanel({ selection, skills, installMethod, onRefresh, onTog
             </button>
           )}
 
-          {skill.isFetched && !skill.isInstalled && (
+          {!skill.isInstalled && (
             <button
               onClick={handleInstall}
               disabled={installing === skill.id}
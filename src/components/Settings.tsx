@@ -1,12 +1,22 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
 import { X, Terminal, Copy } from "lucide-react";
 import { useSettings } from "../hooks/useSettings";
+import { AgentTarget } from "../types/skill";
 
 interface SettingsProps {
   onClose: () => void;
 }
 
 export function Settings({ onClose }: SettingsProps) {
-  const { settings, setInstallMethod } = useSettings();
+  const { settings, setInstallMethod, setNpxFallback, toggleAgent } = useSettings();
+  const [agents, setAgents] = useState<AgentTarget[]>([]);
+
+  useEffect(() => {
+    invoke<AgentTarget[]>("get_agent_targets").then(setAgents).catch(() => setAgents([]));
+  }, []);
+
+  const enabledAgents = settings.enabledAgents ?? ["claude-code"];
 
   return (
     <div className="fixed inset-0 bg-black/50 backdrop-blur-sm flex items-center justify-center p-4 z-50">
@@ -40,9 +50,9 @@ export function Settings({ onClose }: SettingsProps) {
                 />
                 <Terminal className="w-5 h-5 text-[var(--text-secondary)] mt-0.5" />
                 <div>
-                  <div className="font-medium text-[var(--text-primary)]">npx skills add</div>
+                  <div className="font-medium text-[var(--text-primary)]">Native install</div>
                   <div className="text-sm text-[var(--text-muted)]">
-                    Use the official skills CLI (recommended)
+                    Resolve and place skills like npx skills add, without Node (recommended)
                   </div>
                 </div>
               </label>
@@ -59,11 +69,43 @@ export function Settings({ onClose }: SettingsProps) {
                 <div>
                   <div className="font-medium text-[var(--text-primary)]">Direct Copy</div>
                   <div className="text-sm text-[var(--text-muted)]">
-                    Copy skill files directly to ~/.claude/skills
+                    Copy skill files directly into each agent's skills folder
                   </div>
                 </div>
               </label>
             </div>
+            <label className="flex items-center gap-3 mt-4 text-sm text-[var(--text-secondary)] cursor-pointer">
+              <input
+                type="checkbox"
+                checked={settings.npxFallback ?? false}
+                onChange={(e) => setNpxFallback(e.target.checked)}
+                className="accent-[var(--accent)]"
+              />
+              Fall back to npx skills add if the native install fails
+            </label>
+          </div>
+
+          <div className="mt-6">
+            <label className="block text-sm font-medium text-[var(--text-primary)] mb-3">
+              Install For
+            </label>
+            <div className="space-y-2">
+              {agents.map((agent) => (
+                <label
+                  key={agent.id}
+                  className="flex items-center gap-3 text-sm text-[var(--text-secondary)] cursor-pointer"
+                >
+                  <input
+                    type="checkbox"
+                    checked={enabledAgents.includes(agent.id)}
+                    onChange={() => toggleAgent(agent.id)}
+                    className="accent-[var(--accent)]"
+                  />
+                  <span className="text-[var(--text-primary)]">{agent.name}</span>
+                  <span className="text-[var(--text-muted)]">{agent.userDir}</span>
+                </label>
+              ))}
+            </div>
           </div>
         </div>
       </div>
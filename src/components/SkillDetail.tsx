@@ -51,7 +51,14 @@ export function SkillDetail({ skill, installMethod, onClose, onInstallChange }:
   const handleUninstall = async () => {
     setError(null);
     try {
-      await invoke("uninstall_skill", { skillName: skill.name });
+      const dependents = await invoke<string[]>("get_skill_dependents", { skillName: skill.name });
+      if (
+        dependents.length > 0 &&
+        !window.confirm(`${skill.name} is required by ${dependents.join(", ")}. Uninstall anyway?`)
+      ) {
+        return;
+      }
+      await invoke("uninstall_skill", { skillName: skill.name, force: true });
       onInstallChange();
     } catch (e) {
       setError(String(e));
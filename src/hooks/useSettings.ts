@@ -4,6 +4,7 @@ import { Settings, InstallMethod } from "../types/skill";
 
 const defaultSettings: Settings = {
   installMethod: "copy",
+  enabledAgents: ["claude-code"],
 };
 
 export function useSettings() {
@@ -28,5 +29,21 @@ export function useSettings() {
     [updateSettings]
   );
 
-  return { settings, loading, setInstallMethod };
+  const setNpxFallback = useCallback(
+    (npxFallback: boolean) => updateSettings({ npxFallback }),
+    [updateSettings]
+  );
+
+  const toggleAgent = useCallback(
+    (agentId: string) => {
+      const enabled = settings.enabledAgents ?? defaultSettings.enabledAgents ?? [];
+      const enabledAgents = enabled.includes(agentId)
+        ? enabled.filter((id) => id !== agentId)
+        : [...enabled, agentId];
+      return updateSettings({ enabledAgents });
+    },
+    [settings, updateSettings]
+  );
+
+  return { settings, loading, setInstallMethod, setNpxFallback, toggleAgent };
 }
@@ -9,6 +9,20 @@ export interface Skill {
   content?: string;
   isInstalled: boolean;
   isFetched: boolean;
+  dependencies: string[];
+  isDisabled: boolean;
+  installs?: number;
+  recentInstalls?: number;
+}
+
+export interface DependencyNode {
+  reference: string;
+  skillId?: string;
+  name: string;
+  isInstalled: boolean;
+  isResolved: boolean;
+  isCycle: boolean;
+  dependencies: DependencyNode[];
 }
 
 export interface SkillRepository {
@@ -36,12 +50,60 @@ export interface CatalogSkill {
 
 export interface FetchedRepos {
   repos: Record<string, string>; // "owner/repo" -> lastFetched ISO date
+  commits: Record<string, string>; // "owner/repo" -> fetched commit SHA
 }
 
 export type InstallMethod = "npx" | "copy";
 
 export interface Settings {
   installMethod: InstallMethod;
+  npxFallback?: boolean;
+  enabledAgents?: string[];
+  projectPath?: string;
+  trashRetentionDays?: number;
+  catalogUrl?: string | null;
+  catalogSources?: CatalogSource[];
+  indexUrl?: string | null;
+  popularitySource?: string | null;
+}
+
+// Earlier sources take precedence when catalogs list the same repo
+export interface CatalogSource {
+  name: string;
+  kind: "bundled" | "remote" | "file";
+  location?: string;
+  enabled: boolean;
+}
+
+export interface AgentTarget {
+  id: string;
+  name: string;
+  userDir: string;
+  projectDir: string;
+  enabled: boolean;
+}
+
+export interface TargetStatus {
+  agent: string;
+  scope: "user" | "project";
+  path: string;
+  success: boolean;
+  error?: string;
+}
+
+export interface InstalledSkill {
+  name: string;
+  targets: TargetStatus[];
+}
+
+export interface InstallResult {
+  skillName: string;
+  method: "copy" | "native" | "npx";
+  targets: TargetStatus[];
+  filesWritten: string[];
+  dependencies: string[];
+  warnings: string[];
+  output?: string;
 }
 
 export interface RepoGroup {
@@ -58,6 +120,26 @@ export interface RepoInfo {
   isFetched: boolean;
   isCustom: boolean;
   highlight: boolean;
+  catalogs: string[];
+  description?: string;
+  tags: string[];
+  category?: string;
+  maintainer?: string;
+  license?: string;
+  verified: boolean;
+  official: boolean;
+  skillsPath?: string;
+  installs?: number;
+  recentInstalls?: number;
+}
+
+export interface RepoFilter {
+  query?: string;
+  tags?: string[];
+  category?: string;
+  catalog?: string;
+  verifiedOnly?: boolean;
+  officialOnly?: boolean;
 }
 
 export type Selection =
@@ -69,3 +151,311 @@ export interface Favorites {
   skills: string[];  // skill IDs
   repos: string[];   // "owner/repo" keys
 }
+
+export interface BundleSkill {
+  id: string;  // "owner/repo/skill"
+  ref?: string;  // branch, tag or commit SHA
+}
+
+export interface Bundle {
+  name: string;
+  description: string;
+  skills: BundleSkill[];
+  isCatalog: boolean;
+}
+
+export type BundleMemberState = "installed" | "missing" | "outdated" | "unavailable" | "error";
+
+export interface BundleMemberStatus {
+  id: string;
+  ref?: string;
+  name?: string;
+  state: BundleMemberState;
+  message?: string;
+}
+
+export interface SkillOrigin {
+  skillId: string;
+  matchedBy: "hash" | "name";
+  isModified: boolean | null; // null when there is no upstream copy to compare with
+}
+
+export interface InventoryEntry {
+  folder: string;
+  agent: string;
+  scope: "user" | "project";
+  path: string;
+  isSymlink: boolean;
+  isDisabled: boolean;
+  isValid: boolean;
+  name?: string;
+  description?: string;
+  dependencies: string[];
+  sizeBytes: number;
+  fileCount: number;
+  contentHash?: string;
+  origin?: SkillOrigin;
+  isOrphaned: boolean;
+}
+
+export interface TrashEntry {
+  id: string;
+  skillName: string;
+  agent: string;
+  scope: "user" | "project";
+  originalPath: string;
+  trashedAt: number;
+  expiresAt: number;
+}
+
+export interface BackupSkill {
+  folder: string;
+  agent: string;
+  scope: string;
+  isDisabled: boolean;
+  archivePath: string;
+  contentHash: string;
+}
+
+export interface BackupManifest {
+  version: number;
+  createdAt: string;
+  skills: BackupSkill[];
+  configFiles: string[];
+}
+
+export type BackupConflictMode = "skip" | "overwrite" | "rename";
+
+export interface ImportSummary {
+  restored: string[];
+  renamed: string[];
+  skipped: string[];
+  failed: string[];
+  configFiles: string[];
+}
+
+export type BulkOperation = "install" | "uninstall" | "update" | "refresh" | "health-check";
+
+// Payload of the `bulk-progress` event
+export interface BulkProgress {
+  operation: BulkOperation;
+  item: string;
+  index: number;
+  total: number;
+  state: "started" | "succeeded" | "failed";
+  error?: string;
+}
+
+export interface BulkItemResult {
+  item: string;
+  success: boolean;
+  error?: string;
+}
+
+export interface BulkSummary {
+  operation: BulkOperation;
+  total: number;
+  succeeded: number;
+  failed: number;
+  results: BulkItemResult[];
+}
+
+export interface FrontmatterChange {
+  field: "name" | "description" | "dependencies";
+  oldValue: string;
+  newValue: string;
+}
+
+export interface SkillUpdate {
+  skillId: string;
+  name: string;
+  installedPath: string;
+  installedHash: string;
+  upstreamHash: string;
+  installedCommit?: string;
+  upstreamCommit?: string;
+  isModified: boolean;
+  addedFiles: string[];
+  removedFiles: string[];
+  modifiedFiles: string[];
+  frontmatterChanges: FrontmatterChange[];
+}
+
+export type DiffSide =
+  | { kind: "installed"; skillName: string; agent?: string; scope?: "user" | "project" }
+  | { kind: "cached"; skillId: string }
+  | { kind: "commit"; skillId: string; ref: string };
+
+export interface DiffLine {
+  kind: "context" | "added" | "removed";
+  content: string;
+  oldLine?: number;
+  newLine?: number;
+}
+
+export interface DiffHunk {
+  oldStart: number;
+  oldLines: number;
+  newStart: number;
+  newLines: number;
+  lines: DiffLine[];
+}
+
+export interface FileDiff {
+  path: string;
+  status: "added" | "removed" | "modified";
+  isBinary: boolean;
+  hunks: DiffHunk[];
+}
+
+export interface SkillDiff {
+  leftLabel: string;
+  rightLabel: string;
+  files: FileDiff[];
+}
+
+export interface SkillTemplate {
+  id: string;
+  name: string;
+  description: string;
+  isBuiltin: boolean;
+}
+
+export interface CreateSkillRequest {
+  name: string;
+  description: string;
+  template?: string;
+  includeScripts?: boolean;
+  includeReferences?: boolean;
+  install?: boolean;
+  scope?: "user" | "project";
+}
+
+export interface CreatedSkill {
+  name: string;
+  paths: string[];
+  files: string[];
+}
+
+export interface ForkInfo {
+  name: string;
+  path: string;
+  upstreamId: string;
+  upstreamName: string;
+  upstreamCommit?: string;
+  forkedAt: string;
+  mergedAt?: string;
+}
+
+export interface ForkStatus {
+  fork: ForkInfo;
+  localChanges: FileDiff[];
+  upstreamChanges: FileDiff[];
+}
+
+export interface MergeResult {
+  updated: string[];
+  removed: string[];
+  conflicts: string[];
+  upstreamCommit?: string;
+}
+
+export interface CatalogInfo {
+  name: string;
+  source: "remote" | "bundled" | "file";
+  version: string;
+  lastUpdated: string;
+  url?: string;
+  fetchedAt?: string;
+  repoCount: number;
+  error?: string;
+}
+
+export interface RepoHealth {
+  repo: string;
+  status: "ok" | "moved" | "missing" | "empty" | "stale" | "error";
+  httpStatus?: number;
+  movedTo?: string;
+  skillCount?: number;
+  lastCommit?: string;
+  lastCommitAt?: string;
+  daysSinceCommit?: number;
+  suggestion?: "remove" | "update-url" | "review";
+  message?: string;
+}
+
+export interface CatalogHealthReport {
+  checkedAt: string;
+  staleDays: number;
+  summary: {
+    total: number;
+    ok: number;
+    moved: number;
+    missing: number;
+    empty: number;
+    stale: number;
+    errors: number;
+  };
+  repos: RepoHealth[];
+}
+
+export interface GitHubRepoInfo {
+  stars: number;
+  forks: number;
+  openIssues: number;
+  topics: string[];
+  license?: string;
+  archived: boolean;
+  defaultBranch?: string;
+  pushedAt?: string;
+  contributors?: number;
+  fetchedAt: string;
+}
+
+export interface RepoMetadata {
+  owner: string;
+  repo: string;
+  isFetched: boolean;
+  fetchedAt?: string;
+  licenseFile?: string;
+  licenseType?: string;
+  lastCommit?: string;
+  lastCommitAt?: string;
+  commitCount?: number;
+  skillCount: number;
+  fileCount: number;
+  totalSize: number;
+  github?: GitHubRepoInfo;
+  githubError?: string;
+}
+
+export interface PopularityInfo {
+  source: string;
+  fetchedAt: string;
+  updatedAt?: string;
+  skillCount: number;
+  previousFetchedAt?: string;
+}
+
+export interface PublishSkillRequest {
+  source: string;
+  remote: string;
+  skillsPath?: string;
+  branch?: string;
+  baseBranch?: string;
+  message?: string;
+  openPullRequest?: boolean;
+}
+
+export interface PublishResult {
+  name: string;
+  remote: string;
+  branch: string;
+  baseBranch?: string;
+  path: string;
+  commit: string;
+  isUpdate: boolean;
+  files: string[];
+  pullRequestUrl?: string;
+  pullRequestError?: string;
+}